@@ -49,4 +49,127 @@ pub enum AuctionError {
     #[msg("Fee has not yet been delivered")]
     FeeHasNotBeenDelivered,
 
+    #[msg("Remaining accounts must be given as (safety deposit box, store, destination) triples")]
+    InvalidRemainingAccounts,
+
+    #[msg("The safety deposit box does not belong to the auctioned vault")]
+    SafetyDepositBoxNotInVault,
+
+    #[msg("The token account is not the store of the safety deposit box")]
+    SafetyDepositBoxStoreMismatch,
+
+    #[msg("The safety deposit box has already been claimed")]
+    SafetyDepositBoxAlreadyClaimed,
+
+    #[msg("The destination token account mint does not match the safety deposit box mint")]
+    DestinationMintMismatch,
+
+    #[msg("The destination token account is not owned by the auction winner")]
+    DestinationNotOwnedByWinner,
+
+    #[msg("The account is not an initialised safety deposit box")]
+    InvalidSafetyDepositBox,
+
+    #[msg("The account is not an initialised token vault")]
+    InvalidVaultAccount,
+
+    #[msg("The account is not an initialised external price account")]
+    InvalidPriceAccount,
+
+    #[msg("Accounts owned by external programs cannot be written to")]
+    ExternalAccountIsReadOnly,
+
+    #[msg("The given program is not the token vault program")]
+    InvalidVaultProgram,
+
+    #[msg("The given vault PDA is not derived from the vault")]
+    InvalidVaultPda,
+
+    #[msg("The price program is already trusted")]
+    PriceProgramAlreadyTrusted,
+
+    #[msg("Too many trusted price programs")]
+    TooManyTrustedPricePrograms,
+
+    #[msg("The external price account is not owned by a trusted price program")]
+    UntrustedPriceProgram,
+
+    #[msg("The payment mint is not the external price account's price mint")]
+    PaymentMintDoesNotMatchPriceMint,
+
+    #[msg("The given program is not the price tracker program")]
+    InvalidPriceProgram,
+
+    #[msg("A vault must be split into at least one fraction")]
+    InvalidNumberOfFractions,
+
+    #[msg("The vault can be combined and so it can no longer be released")]
+    VaultAllowedToCombine,
+
+    #[msg("The given auction account is not derived from the vault")]
+    InvalidAuctionAccount,
+
+    #[msg("An auction has already been started for the vault")]
+    AuctionAlreadyStarted,
+
+    #[msg("The given temporary token account is not derived from the payer")]
+    InvalidTemporaryTokenAccount,
+
+    #[msg("The token account is not of the auction's payment mint")]
+    TokenAccountMintMismatch,
+
+    #[msg("The token account is not owned by the expected wallet")]
+    TokenAccountOwnerMismatch,
+
+    #[msg("The bid account does not belong to the bidder and auction")]
+    InvalidBidAccount,
+
+    #[msg("The bid token account does not belong to the bid")]
+    BidTokenAccountMismatch,
+
+    #[msg("Contributions cannot be withdrawn while the pool holds the top bid")]
+    PoolHoldsTopBid,
+
+    #[msg("The given program is not the auction program")]
+    InvalidAuctionProgram,
+
+    #[msg("The credit for the escrowed fractions leaves too little of the bid to combine the vault")]
+    FractionCreditTooLarge,
+
+    #[msg("The fraction account is not the one registered with the bid")]
+    FractionAccountMismatch,
+
+    #[msg("The listing has not received its first bid yet")]
+    AuctionNotCombined,
+
+    #[msg("The listing has already received its first bid")]
+    AuctionAlreadyCombined,
+
+    #[msg("Offers can only be made on vaults priced by the price tracker")]
+    PriceNotTracked,
+
+    #[msg("The offer has expired")]
+    OfferHasExpired,
+
+    #[msg("The offer has not expired yet")]
+    OfferHasNotExpired,
+
+    #[msg("The offer has already been accepted")]
+    OfferAlreadyAccepted,
+
+    #[msg("The offer has not been accepted by a majority of the fractions")]
+    OfferLacksMajority,
+
+    #[msg("Cannot withdraw more votes than were cast")]
+    InsufficientVotes,
+
+    #[msg("The refund account is not the one registered with the bid")]
+    RefundAccountMismatch,
+
+    #[msg("The settings and payment treasury are not those of the listing")]
+    ListingMismatch,
+
+    #[msg("Offers need a warm-up period of at least a second")]
+    InvalidOfferWarmUpPeriod,
+
 }
\ No newline at end of file
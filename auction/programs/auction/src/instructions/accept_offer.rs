@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+
+use crate::errors::*;
+use crate::state::{Offer, OfferVote, Settings};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+#[instruction(vote_bump: u8)]
+pub struct AcceptOffer<'info> {
+
+    #[account(
+        mut,
+        seeds = [b"offer".as_ref(), offer.vault.as_ref(), offer.bidder.as_ref()],
+        bump = offer.bump,
+        has_one = settings,
+        has_one = fraction_account
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + OfferVote::space(),
+        seeds = [b"offer_vote".as_ref(), offer.key().as_ref(), voter.key().as_ref()],
+        bump = vote_bump,
+    )]
+    pub offer_vote: Box<Account<'info, OfferVote>>,
+
+    // The offer's warm-up period for acceptances.
+    pub settings: Box<Account<'info, Settings>>,
+
+    // The token program makes sure both accounts are of the fraction mint.
+    #[account(mut)]
+    pub fraction_account: Box<Account<'info, TokenAccount>>,
+
+    // The token program makes sure the voter is the owner or a delegate.
+    #[account(mut)]
+    pub voter_fraction_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> AcceptOffer<'info> {
+
+    fn transfer_to_offer(&self, votes: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.voter_fraction_account.to_account_info(),
+                    to: self.fraction_account.to_account_info(),
+                    authority: self.voter.to_account_info()
+                }
+            ),
+            votes
+        )?;
+        Ok(())
+    }
+
+}
+
+pub fn handler(ctx: Context<AcceptOffer>, vote_bump: u8, votes: u64) -> ProgramResult {
+    let offer = &ctx.accounts.offer;
+
+    utils::assert_offer_has_not_expired(offer)?;
+    utils::assert_offer_not_accepted(offer)?;
+
+    if votes > 0 {
+        ctx.accounts.transfer_to_offer(votes)?;
+    }
+
+    // Count the voter's earlier acceptances once warmed up, before restarting the warm-up for new ones
+    let current_timestamp = utils::get_current_timestamp()?;
+    let warm_up_period = ctx.accounts.settings.offer_warm_up_period;
+    let offer_vote = &mut ctx.accounts.offer_vote;
+    let activated = offer_vote.activate_votes(warm_up_period, current_timestamp)?;
+
+    offer_vote.offer = ctx.accounts.offer.key();
+    offer_vote.voter = ctx.accounts.voter.key();
+    offer_vote.bump = vote_bump;
+    if votes > 0 {
+        offer_vote.pending_votes = match offer_vote.pending_votes.checked_add(votes) {
+            Some(val) => val,
+            None => return Err(AuctionError::NumericalOverflowError.into()),
+        };
+        offer_vote.last_vote_timestamp = current_timestamp;
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.votes = match offer.votes.checked_add(activated) {
+        Some(val) => val,
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    };
+
+    Ok(())
+}
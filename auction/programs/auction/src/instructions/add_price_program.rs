@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::state::MAX_TRUSTED_PRICE_PROGRAMS;
+use crate::instructions::set_owner::SetAuthority;
+
+pub fn handler(
+    ctx: Context<SetAuthority>,
+    price_program: Pubkey
+) -> ProgramResult {
+    let authority = &mut ctx.accounts.authority;
+
+    if authority.trusted_price_programs.contains(&price_program) {
+        return Err(AuctionError::PriceProgramAlreadyTrusted.into());
+    }
+
+    if authority.trusted_price_programs.len() >= MAX_TRUSTED_PRICE_PROGRAMS {
+        return Err(AuctionError::TooManyTrustedPricePrograms.into());
+    }
+
+    authority.trusted_price_programs.push(price_program);
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, Mint, TokenAccount};
-use anchor_lang::solana_program;
 
 use crate::state::{Auction, Authority, Settings};
-use crate::vault::{Vault, create_withdraw_tokens_instruction};
+use crate::vault::{Vault, SafetyDepositBox};
+use crate::vault_instructions::{self, WithdrawTokenFromSafetyDepositBox};
 use crate::instructions::utils;
 
 #[derive(Accounts)]
@@ -32,7 +32,8 @@ pub struct Claim<'info> {
     #[account(mut)]
     pub destination_token_account: Box<Account<'info, TokenAccount>>,
 
-    pub bidder: Signer<'info>,
+    // The auction winner, i.e. the beneficiary of the top bid.
+    pub beneficiary: Signer<'info>,
 
     // ------------------------------------------------------------------
     // Additional accounts needed for the withdraw from vault invocation:
@@ -41,10 +42,10 @@ pub struct Claim<'info> {
     pub token_program: Program<'info, Token>,
 
     #[account(mut)]
-    pub locked_token_account: AccountInfo<'info>,
+    pub locked_token_account: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub safety_deposit_box: AccountInfo<'info>,
+    pub safety_deposit_box: Box<Account<'info, SafetyDepositBox>>,
 
     pub vault_pda: AccountInfo<'info>,
 
@@ -59,10 +60,10 @@ pub struct Claim<'info> {
 
 impl<'info> Claim<'info> {
 
-    fn withdraw_context(&self) -> CpiContext<'_, '_, '_, 'info, Withdraw<'info>> {
+    fn withdraw_context(&self) -> CpiContext<'_, '_, '_, 'info, WithdrawTokenFromSafetyDepositBox<'info>> {
         CpiContext::new(
             self.vault_program.clone(),
-            Withdraw {
+            WithdrawTokenFromSafetyDepositBox {
                 vault: self.vault.to_account_info(),
                 vault_authority: self.authority.to_account_info(),
                 vault_pda: self.vault_pda.to_account_info(),
@@ -78,67 +79,42 @@ impl<'info> Claim<'info> {
 
 }
 
-#[derive(Accounts)]
-struct Withdraw<'info> {
-    pub vault: AccountInfo<'info>,
-    pub vault_authority: AccountInfo<'info>,
-    pub vault_pda: AccountInfo<'info>,
-    pub destination_token_account: AccountInfo<'info>,
-    pub safety_deposit_box: AccountInfo<'info>,
-    pub locked_token_account: AccountInfo<'info>,
-    pub fraction_mint: AccountInfo<'info>,
-    pub rent: AccountInfo<'info>,
-    pub token_program: AccountInfo<'info>
-}
-
-
-fn withdraw_locked<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>, amount: u64) -> ProgramResult {
-    let ix = create_withdraw_tokens_instruction(
-        Vault::owner(),
-        *ctx.accounts.destination_token_account.key,
-        *ctx.accounts.safety_deposit_box.key,
-        *ctx.accounts.locked_token_account.key,
-        *ctx.accounts.vault.key,
-        *ctx.accounts.fraction_mint.key,
-        *ctx.accounts.vault_authority.key,
-        *ctx.accounts.vault_pda.key,
-        amount,
-    );
-    solana_program::program::invoke_signed(
-        &ix,
-        &[
-            ctx.accounts.destination_token_account.to_account_info(),
-            ctx.accounts.safety_deposit_box.to_account_info(),
-            ctx.accounts.locked_token_account.to_account_info(),
-            ctx.accounts.vault.to_account_info(),
-            ctx.accounts.fraction_mint.to_account_info(),
-            ctx.accounts.vault_authority.to_account_info(),
-            ctx.accounts.vault_pda.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.rent.to_account_info(),
-            ctx.program.to_account_info(),
-        ],
-        ctx.signer_seeds
-    )
-
-}
-
 pub fn handler(ctx: Context<Claim>, amount: u64) -> ProgramResult {
     let auction = &ctx.accounts.auction;
     let authority = &ctx.accounts.authority;
+    let safety_deposit_box = &ctx.accounts.safety_deposit_box;
 
-    // Make sure the auction has in actually ended
+    // Make sure the auction has in actually ended and has been officially closed
     utils::assert_auction_has_ended(auction)?;
+    utils::assert_fee_delivered(auction)?;
 
     // Make sure the claimer is indeed the auction winner
-    utils::assert_auction_winner(&ctx.accounts.bidder.key(), &auction.top_bidder)?;
+    utils::assert_auction_winner(&ctx.accounts.beneficiary.key(), &auction.top_bidder)?;
+
+    // Make sure the withdrawal goes through the genuine token vault program
+    utils::assert_vault_program(&ctx.accounts.vault_program)?;
+    utils::assert_vault_pda(&auction.vault, &ctx.accounts.vault_pda)?;
 
-    withdraw_locked(
+    // Make sure the box is one of the vault's and that the winner is the one receiving its contents
+    utils::assert_safety_deposit_box_in_vault(safety_deposit_box, &auction.vault)?;
+    utils::assert_safety_deposit_box_store(safety_deposit_box, &ctx.accounts.locked_token_account.key())?;
+    utils::assert_safety_deposit_box_not_claimed(auction, safety_deposit_box)?;
+    utils::assert_claim_destination(&ctx.accounts.destination_token_account, safety_deposit_box, &auction.top_bidder)?;
+
+    vault_instructions::withdraw_token_from_safety_deposit_box(
         ctx.accounts
             .withdraw_context()
             .with_signer(&[&[b"authority".as_ref(), crate::id().as_ref(), &[authority.bump]]]),
         amount
     )?;
 
+    // Once the box has been emptied it is recorded as claimed
+    ctx.accounts.locked_token_account.reload()?;
+    if ctx.accounts.locked_token_account.amount == 0 {
+        let order = ctx.accounts.safety_deposit_box.order;
+        let auction = &mut ctx.accounts.auction;
+        auction.mark_box_claimed(order);
+    }
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Mint, TokenAccount};
+
+use crate::errors::*;
+use crate::state::{Auction, Authority, Settings};
+use crate::vault::{Vault, SafetyDepositBox};
+use crate::vault_instructions::{self, WithdrawTokenFromSafetyDepositBox};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct ClaimAll<'info> {
+
+    #[account(
+        mut,
+        seeds = [b"authority".as_ref(), crate::id().as_ref()],
+        bump = authority.bump
+    )]
+    pub authority: Box<Account<'info, Authority>>,
+
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), vault.key().as_ref()],
+        bump = auction.bump,
+        has_one = settings
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    pub settings: Box<Account<'info, Settings>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    // The auction winner, i.e. the beneficiary of the top bid.
+    pub beneficiary: Signer<'info>,
+
+    // ------------------------------------------------------------------
+    // Additional accounts needed for the withdraw from vault invocations:
+    // ------------------------------------------------------------------
+
+    pub token_program: Program<'info, Token>,
+
+    pub vault_pda: AccountInfo<'info>,
+
+    pub vault_program: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    // The safety deposit boxes are passed through the remaining accounts
+    // as (safety_deposit_box, locked_token_account, destination_token_account)
+    // triples, one for each box to be emptied.
+
+}
+
+impl<'info> ClaimAll<'info> {
+
+    fn withdraw_context(
+        &self,
+        safety_deposit_box: &AccountInfo<'info>,
+        locked_token_account: &AccountInfo<'info>,
+        destination_token_account: &AccountInfo<'info>
+    ) -> CpiContext<'_, '_, '_, 'info, WithdrawTokenFromSafetyDepositBox<'info>> {
+        CpiContext::new(
+            self.vault_program.clone(),
+            WithdrawTokenFromSafetyDepositBox {
+                vault: self.vault.to_account_info(),
+                vault_authority: self.authority.to_account_info(),
+                vault_pda: self.vault_pda.to_account_info(),
+                destination_token_account: destination_token_account.clone(),
+                safety_deposit_box: safety_deposit_box.clone(),
+                locked_token_account: locked_token_account.clone(),
+                fraction_mint: self.fraction_mint.to_account_info(),
+                rent: self.rent.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            }
+        )
+    }
+
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAll<'info>>) -> ProgramResult {
+    let auction = &ctx.accounts.auction;
+    let authority_bump = ctx.accounts.authority.bump;
+
+    // Make sure the auction has in actually ended and has been officially closed
+    utils::assert_auction_has_ended(auction)?;
+    utils::assert_fee_delivered(auction)?;
+
+    // Make sure the claimer is indeed the auction winner
+    utils::assert_auction_winner(&ctx.accounts.beneficiary.key(), &auction.top_bidder)?;
+
+    // Make sure the withdrawals go through the genuine token vault program
+    utils::assert_vault_program(&ctx.accounts.vault_program)?;
+    utils::assert_vault_pda(&auction.vault, &ctx.accounts.vault_pda)?;
+
+    let chunks = ctx.remaining_accounts.chunks_exact(3);
+    if !chunks.remainder().is_empty() {
+        return Err(AuctionError::InvalidRemainingAccounts.into());
+    }
+
+    for accounts in chunks {
+        let safety_deposit_box_info = &accounts[0];
+        let locked_token_account_info = &accounts[1];
+        let destination_token_account_info = &accounts[2];
+
+        let safety_deposit_box: Account<SafetyDepositBox> = Account::try_from(safety_deposit_box_info)?;
+        let locked_token_account: Account<TokenAccount> = Account::try_from(locked_token_account_info)?;
+        let destination_token_account: Account<TokenAccount> = Account::try_from(destination_token_account_info)?;
+
+        // Make sure the box is one of the vault's and that the winner is the one receiving its contents
+        let auction = &ctx.accounts.auction;
+        utils::assert_safety_deposit_box_in_vault(&safety_deposit_box, &auction.vault)?;
+        utils::assert_safety_deposit_box_store(&safety_deposit_box, &locked_token_account.key())?;
+        utils::assert_safety_deposit_box_not_claimed(auction, &safety_deposit_box)?;
+        utils::assert_claim_destination(&destination_token_account, &safety_deposit_box, &auction.top_bidder)?;
+
+        vault_instructions::withdraw_token_from_safety_deposit_box(
+            ctx.accounts
+                .withdraw_context(safety_deposit_box_info, locked_token_account_info, destination_token_account_info)
+                .with_signer(&[&[b"authority".as_ref(), crate::id().as_ref(), &[authority_bump]]]),
+            locked_token_account.amount
+        )?;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.mark_box_claimed(safety_deposit_box.order);
+    }
+
+    Ok(())
+}
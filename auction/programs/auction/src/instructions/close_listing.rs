@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, CloseAccount};
+
+use crate::state::Auction;
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct CloseListing<'info> {
+
+    // Closed so that the vault, which is still active, can be listed or auctioned again.
+    #[account(
+        mut,
+        seeds = [b"auction".as_ref(), auction.vault.as_ref()],
+        bump = auction.bump,
+        has_one = payment_treasury,
+        has_one = lister,
+        close = lister
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(mut)]
+    pub payment_treasury: Box<Account<'info, TokenAccount>>,
+
+    // Paid the rent for the listing, which is refunded to them.
+    #[account(mut)]
+    pub lister: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+impl<'info> CloseListing<'info> {
+
+    fn close_payment_treasury(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.payment_treasury.to_account_info(),
+                    destination: self.lister.clone(),
+                    authority: self.auction.to_account_info()
+                },
+                &[&[b"auction".as_ref(), self.auction.vault.as_ref(), &[self.auction.bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
+}
+
+pub fn handler(ctx: Context<CloseListing>) -> ProgramResult {
+    let auction = &ctx.accounts.auction;
+
+    // Only a listing which expired without any bids can be closed
+    utils::assert_auction_has_ended(auction)?;
+    utils::assert_auction_not_combined(auction)?;
+
+    ctx.accounts.close_payment_treasury()?;
+
+    Ok(())
+}
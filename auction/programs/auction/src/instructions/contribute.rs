@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+
+use crate::errors::*;
+use crate::state::{Auction, Pool, Contribution};
+use crate::native;
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct Contribute<'info> {
+
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), auction.key().as_ref()],
+        bump = pool.bump,
+        has_one = auction,
+        constraint = pool.token_account == pool_token_account.key()
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution".as_ref(), pool.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+        has_one = pool,
+        has_one = contributor
+    )]
+    pub contribution: Box<Account<'info, Contribution>>,
+
+    // Either a token account of the payment mint owned by the contributor or,
+    // when contributing native SOL, the contributor's wallet.
+    #[account(mut)]
+    pub paying_token_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+impl<'info> Contribute<'info> {
+
+    fn transfer_to_pool(&self, amount: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.paying_token_account.clone(),
+                    to: self.pool_token_account.to_account_info(),
+                    authority: self.contributor.to_account_info()
+                }
+            ),
+            amount
+        )?;
+        Ok(())
+    }
+
+    fn wrap_into_pool(&self, amount: u64) -> ProgramResult {
+        native::wrap(
+            &self.contributor.to_account_info(),
+            &self.pool_token_account.to_account_info(),
+            &self.system_program.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount
+        )
+    }
+
+}
+
+pub fn handler(ctx: Context<Contribute>, amount: u64) -> ProgramResult {
+    let auction = &ctx.accounts.auction;
+    let contributor = ctx.accounts.contributor.key();
+
+    utils::assert_auction_has_not_ended(auction)?;
+
+    if native::is_native_payment(&auction.payment_mint, &ctx.accounts.paying_token_account, &contributor) {
+        ctx.accounts.wrap_into_pool(amount)?;
+    } else {
+        let paying_token_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.paying_token_account)?;
+        utils::assert_token_account_mint(&paying_token_account, &auction.payment_mint)?;
+        utils::assert_sufficient_funds(&paying_token_account, amount)?;
+        ctx.accounts.transfer_to_pool(amount)?;
+    }
+
+    // Record the contribution, both for pro-rata withdrawals and for sharing out whatever the pool wins
+    let contribution = &mut ctx.accounts.contribution;
+    contribution.amount = match contribution.amount.checked_add(amount) {
+        Some(val) => val,
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    };
+    let pool = &mut ctx.accounts.pool;
+    pool.total_contributions = match pool.total_contributions.checked_add(amount) {
+        Some(val) => val,
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    };
+
+    Ok(())
+}
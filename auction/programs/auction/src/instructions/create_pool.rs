@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Mint, TokenAccount};
+
+use crate::state::{Auction, Pool};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+#[instruction(pool_bump: u8)]
+pub struct CreatePool<'info> {
+
+    #[account(has_one = payment_mint)]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::space(),
+        seeds = [b"pool".as_ref(), auction.key().as_ref()],
+        bump = pool_bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    // Holds the contributions, under the control of the pool.
+    #[account(
+        init,
+        payer = payer,
+        token::mint = payment_mint,
+        token::authority = pool
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+pub fn handler(ctx: Context<CreatePool>, pool_bump: u8) -> ProgramResult {
+    let auction = &ctx.accounts.auction;
+
+    // There is no point pooling funds for an auction which is already over
+    utils::assert_auction_has_not_ended(auction)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.auction = auction.key();
+    pool.token_account = ctx.accounts.pool_token_account.key();
+    pool.total_contributions = 0;
+    pool.bump = pool_bump;
+
+    Ok(())
+}
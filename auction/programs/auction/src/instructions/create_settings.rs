@@ -35,18 +35,25 @@ pub fn handler(
     duration: u64,
     soft_close_period: u64,
     bid_increment: u64,
-    facilitator_fee: u64
+    facilitator_fee: u64,
+    offer_warm_up_period: u64
 ) -> ProgramResult {
 
     if facilitator_fee > MAX_FACILITATOR_FEE {
         return Err(AuctionError::InvalidFacilitatorFee.into());
     }
 
+    // Acceptances must not count within the transaction that makes them
+    if offer_warm_up_period == 0 {
+        return Err(AuctionError::InvalidOfferWarmUpPeriod.into());
+    }
+
     let settings = &mut ctx.accounts.settings;
     settings.duration = duration;
     settings.soft_close_period = soft_close_period;
     settings.bid_increment = bid_increment;
     settings.facilitator_fee = facilitator_fee;
+    settings.offer_warm_up_period = offer_warm_up_period;
 
     Ok(())
 }
\ No newline at end of file
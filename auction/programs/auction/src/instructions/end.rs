@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount, CloseAccount};
 
-use crate::state::{Auction, Settings, Authority};
+use crate::state::{Auction, Bid, Settings, Authority};
 use crate::vault::Vault;
+use crate::vault_instructions::{self, RedeemShares};
+use crate::native::{self, Unwrap};
 use crate::instructions::utils;
 
 #[derive(Accounts)]
@@ -26,24 +28,123 @@ pub struct End<'info> {
 
     pub settings: Box<Account<'info, Settings>>,
 
+    #[account(
+        has_one = fraction_mint,
+        has_one = redeem_treasury,
+    )]
     pub vault: Box<Account<'info, Vault>>,
 
     #[account(mut)]
     pub payment_treasury: Box<Account<'info, TokenAccount>>,
 
+    // ---------------------------------------------------------------
+    // The winning bid, whose escrowed fractions are settled on ending:
+    // ---------------------------------------------------------------
+
+    #[account(
+        seeds = [b"bid".as_ref(), auction.top_bidder.as_ref(), auction.key().as_ref()],
+        bump = top_bid.bump
+    )]
+    pub top_bid: Box<Account<'info, Bid>>,
+
     #[account(
         mut,
-        constraint = fee_token_account.mint == auction.payment_mint,
-        constraint = fee_token_account.owner == authority.owner,
+        seeds = [b"escrow".as_ref(), top_bid.key().as_ref()],
+        bump = top_bid.escrow_bump
     )]
-    pub fee_token_account: Box<Account<'info, TokenAccount>>,
+    pub fraction_escrow: Box<Account<'info, TokenAccount>>,
+
+    // Receives the rent of the escrow once it is closed.
+    #[account(mut, constraint = top_bidder.key() == auction.top_bidder)]
+    pub top_bidder: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub redeem_treasury: Box<Account<'info, TokenAccount>>,
+
+    pub vault_pda: AccountInfo<'info>,
+
+    pub vault_program: AccountInfo<'info>,
+
+    // Either a token account of the payment mint owned by the program owner or, 
+    // for native SOL auctions, the owner's wallet.
+    #[account(mut)]
+    pub fee_token_account: AccountInfo<'info>,
+
+    #[account(constraint = auction.payment_mint == payment_mint.key())]
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // Funds the temporary account used to unwrap native SOL fees.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub temporary_token_account: AccountInfo<'info>,
 
     pub token_program: Program<'info, Token>,
 
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
 }
 
 impl<'info> End<'info> {
+
+    fn unwrap_fee_to_owner(&self, fee: u64) -> ProgramResult {
+        Unwrap {
+            temporary_token_account: self.temporary_token_account.clone(),
+            native_mint: self.payment_mint.to_account_info(),
+            payer: self.payer.to_account_info(),
+            destination: self.fee_token_account.clone(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        }.unwrap(
+            &self.payment_treasury.to_account_info(),
+            &self.auction.to_account_info(),
+            &[&[b"auction".as_ref(), self.vault.key().as_ref(), &[self.auction.bump]]],
+            fee
+        )
+    }
     
+    fn redeem_escrowed_fractions(&self) -> ProgramResult {
+        vault_instructions::redeem_shares(
+            CpiContext::new_with_signer(
+                self.vault_program.clone(),
+                RedeemShares {
+                    outstanding_shares_account: self.fraction_escrow.to_account_info(),
+                    proceeds_account: self.payment_treasury.to_account_info(),
+                    fraction_mint: self.fraction_mint.to_account_info(),
+                    redeem_treasury: self.redeem_treasury.to_account_info(),
+                    vault_pda: self.vault_pda.clone(),
+                    burn_authority: self.top_bid.to_account_info(),
+                    vault: self.vault.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                &[&[b"bid".as_ref(), self.top_bid.bidder.as_ref(), self.auction.key().as_ref(), &[self.top_bid.bump]]]
+            )
+        )
+    }
+
+    fn close_fraction_escrow(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.fraction_escrow.to_account_info(),
+                    destination: self.top_bidder.clone(),
+                    authority: self.top_bid.to_account_info()
+                },
+                &[&[b"bid".as_ref(), self.top_bid.bidder.as_ref(), self.auction.key().as_ref(), &[self.top_bid.bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
     fn transfer_fee_to_owner(&self, fee: u64) -> ProgramResult {
         token::transfer(
             CpiContext::new_with_signer(
@@ -69,9 +170,28 @@ pub fn handler(ctx: Context<End>) -> ProgramResult {
     // Make sure this instruction cannot be called multiple times
     utils::assert_fee_not_delivered(auction)?;
     utils::assert_auction_has_ended(auction)?; 
+    utils::assert_auction_combined(auction)?;
+
+    // The winner was credited for their escrowed fractions' share of their bid, so those
+    // fractions are redeemed into the payment treasury rather than paid out to them. What
+    // the treasury holds is then shared out between the remaining fractions as usual.
+    if ctx.accounts.fraction_escrow.amount > 0 {
+        utils::assert_vault_program(&ctx.accounts.vault_program)?;
+        utils::assert_vault_pda(&auction.vault, &ctx.accounts.vault_pda)?;
+        ctx.accounts.redeem_escrowed_fractions()?;
+    }
+    ctx.accounts.close_fraction_escrow()?;
 
     let fee = utils::calculate_fee(auction.top_bid, settings.facilitator_fee)?;
-    ctx.accounts.transfer_fee_to_owner(fee)?;
+    let owner = ctx.accounts.authority.owner;
+    if native::is_native_payment(&auction.payment_mint, &ctx.accounts.fee_token_account, &owner) {
+        ctx.accounts.unwrap_fee_to_owner(fee)?;
+    } else {
+        let fee_token_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.fee_token_account)?;
+        utils::assert_token_account_mint(&fee_token_account, &auction.payment_mint)?;
+        utils::assert_token_account_owner(&fee_token_account, &owner)?;
+        ctx.accounts.transfer_fee_to_owner(fee)?;
+    }
 
     let auction = &mut ctx.accounts.auction;
     auction.fee_paid = true;
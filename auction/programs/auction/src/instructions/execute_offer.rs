@@ -0,0 +1,328 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount, CloseAccount};
+use std::result::Result;
+
+use crate::errors::*;
+use crate::state::{Auction, Bid, Offer, Authority, Settings};
+use crate::vault::Vault;
+use crate::vault_instructions::{self, CombineVault};
+use crate::price_tracker::{self, SettleOffer};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+#[instruction(auction_bump: u8, bid_bump: u8, escrow_bump: u8)]
+pub struct ExecuteOffer<'info> {
+
+    // The program authority is the authority on the vault and as such
+    // it is needed to sign the invocation to the vault program to combine it.
+    #[account(
+        mut,
+        seeds = [b"authority".as_ref(), crate::id().as_ref()],
+        bump = authority.bump
+    )]
+    pub authority: Account<'info, Authority>,
+
+    #[account(
+        mut,
+        seeds = [b"offer".as_ref(), vault.key().as_ref(), bidder.key().as_ref()],
+        bump = offer.bump,
+        has_one = vault,
+        has_one = bidder,
+        has_one = settings,
+        has_one = escrow,
+        has_one = fraction_account,
+        has_one = payment_mint
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    // Pays for the combination out of the escrow.
+    #[account(
+        seeds = [b"offer_authority".as_ref(), offer.key().as_ref()],
+        bump = offer.authority_bump
+    )]
+    pub offer_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    // The accepted fractions stay escrowed for their holders to withdraw and redeem.
+    pub fraction_account: Box<Account<'info, TokenAccount>>,
+
+    // Stands in as the outstanding fractions account of the combination, empty so that
+    // the accepted fractions are paid for like all others. Closed again straight away.
+    #[account(
+        init,
+        payer = payer,
+        token::mint = fraction_mint,
+        token::authority = offer_authority
+    )]
+    pub outstanding_fractions: Box<Account<'info, TokenAccount>>,
+
+    // The accepted offer is settled as an auction which has already ended,
+    // with the offer as its only bid.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Auction::space(),
+        seeds = [b"auction".as_ref(), vault.key().as_ref()],
+        bump = auction_bump,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    pub settings: Box<Account<'info, Settings>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = payment_mint,
+        token::authority = auction
+    )]
+    pub payment_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Bid::space(),
+        seeds = [b"bid".as_ref(), bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bid_bump,
+    )]
+    pub bid: Box<Account<'info, Bid>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = payment_mint,
+        token::authority = bid
+    )]
+    pub bid_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"escrow".as_ref(), bid.key().as_ref()],
+        bump = escrow_bump,
+        token::mint = fraction_mint,
+        token::authority = bid
+    )]
+    pub fraction_escrow: Box<Account<'info, TokenAccount>>,
+
+    // Gets back the rent of the offer's escrow once it has been spent.
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+
+    // Anyone may settle an accepted offer, paying the rent for the new accounts.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // ------
+    // Mints:
+    // ------
+
+    #[account(mut)]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // ------------------------------------------------------------
+    // Additional accounts needed for the combine vault invocation:
+    // ------------------------------------------------------------
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    // Priced at the offer by the price tracker before the combination.
+    #[account(mut)]
+    pub external_pricing_account: AccountInfo<'info>,
+
+    pub price_program: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub vault_fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub vault_redeem_treasury: Box<Account<'info, TokenAccount>>,
+
+    pub vault_pda: AccountInfo<'info>,
+
+    pub vault_program: AccountInfo<'info>,
+
+    // --------------------------------------------------
+    // For account initialisation and token invocations:
+    // --------------------------------------------------
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> ExecuteOffer<'info> {
+
+    fn combine_vault(&self) -> Result<u64, ProgramError> {
+        let balance_before = self.escrow.amount;
+        vault_instructions::combine_vault(
+            CpiContext::new_with_signer(
+                self.vault_program.clone(),
+                CombineVault {
+                    vault: self.vault.to_account_info(),
+                    outstanding_share_token_account: self.outstanding_fractions.to_account_info(),
+                    paying_token_account: self.escrow.to_account_info(),
+                    fraction_mint: self.fraction_mint.to_account_info(),
+                    fraction_treasury: self.vault_fraction_treasury.to_account_info(),
+                    redeem_treasury: self.vault_redeem_treasury.to_account_info(),
+                    new_vault_authority: self.authority.to_account_info(),
+                    vault_authority: self.authority.to_account_info(),
+                    paying_transfer_authority: self.offer_authority.clone(),
+                    vault_pda: self.vault_pda.to_account_info(),
+                    external_pricing_account: self.external_pricing_account.clone(),
+                    token_program: self.token_program.to_account_info(),
+                },
+                &[
+                    &[b"authority".as_ref(), crate::id().as_ref(), &[self.authority.bump]],
+                    &[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]
+                ]
+            )
+        )?;
+        let balance_after = token::accessor::amount(&self.escrow.to_account_info())?;
+        Ok(balance_before - balance_after)
+    }
+
+    fn settle_offer(&self, price_per_share: u64) -> ProgramResult {
+        price_tracker::settle_offer(
+            CpiContext::new_with_signer(
+                self.price_program.clone(),
+                SettleOffer {
+                    price_account: self.external_pricing_account.clone(),
+                    auction_authority: self.authority.to_account_info(),
+                },
+                &[&[b"authority".as_ref(), crate::id().as_ref(), &[self.authority.bump]]]
+            ),
+            price_per_share
+        )
+    }
+
+    fn close_outstanding_fractions(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.outstanding_fractions.to_account_info(),
+                    destination: self.payer.to_account_info(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
+    fn transfer_to_treasury(&self, amount: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.escrow.to_account_info(),
+                    to: self.payment_treasury.to_account_info(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            ),
+            amount
+        )?;
+        Ok(())
+    }
+
+    fn close_escrow(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.escrow.to_account_info(),
+                    destination: self.bidder.clone(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
+}
+
+pub fn handler(ctx: Context<ExecuteOffer>, auction_bump: u8, bid_bump: u8, escrow_bump: u8) -> ProgramResult {
+    let vault = &ctx.accounts.vault;
+    let authority = &ctx.accounts.authority;
+    let offer = &ctx.accounts.offer;
+
+    // Only an offer which a majority of the fractions in circulation has accepted before it expired
+    let circulating_supply = utils::calculate_circulating_supply(ctx.accounts.fraction_mint.supply, &ctx.accounts.vault_fraction_treasury, 0)?;
+    utils::assert_offer_has_not_expired(offer)?;
+    utils::assert_offer_not_accepted(offer)?;
+    utils::assert_offer_has_majority(offer, circulating_supply)?;
+
+    // Check the vault information is correct, the price tracker's own votes have no say in it
+    utils::assert_vault_program(&ctx.accounts.vault_program)?;
+    utils::assert_vault_pda(&vault.key(), &ctx.accounts.vault_pda)?;
+    utils::assert_vault_owned_by_auction_program_authority(vault, authority)?;
+    utils::assert_correct_pricing_account(vault, &ctx.accounts.external_pricing_account)?;
+    utils::assert_price_tracked(&ctx.accounts.external_pricing_account)?;
+    utils::assert_price_program(&ctx.accounts.price_program)?;
+    utils::assert_vault_in_active_state(vault)?;
+
+    // Combine the vault out of the escrow, at the offer's price net of the facilitator fee
+    let settings = &ctx.accounts.settings;
+    let offer_amount = offer.amount;
+    let price_per_share = utils::calculate_offer_price_per_fraction(offer_amount, settings.facilitator_fee, circulating_supply)?;
+    ctx.accounts.settle_offer(price_per_share)?;
+    let reserve_price = ctx.accounts.combine_vault()?;
+    ctx.accounts.close_outstanding_fractions()?;
+
+    let minimum_bid = utils::calculate_minimum_starting_bid(reserve_price, settings.facilitator_fee)?;
+    utils::assert_bid_meets_reserve_price(offer_amount, minimum_bid)?;
+
+    // The rest of the offer goes to the payment treasury, just like the rest of a winning bid
+    let remainder = match offer_amount.checked_sub(reserve_price) {
+        Some(val) => val,
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    };
+    ctx.accounts.transfer_to_treasury(remainder)?;
+    ctx.accounts.close_escrow()?;
+
+    let bid = &mut ctx.accounts.bid;
+    let auction = &mut ctx.accounts.auction;
+    let current_timestamp = utils::get_current_timestamp()?;
+
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.auction = auction.key();
+    bid.amount = offer_amount;
+    bid.timestamp = current_timestamp;
+    bid.withdrawable = false;
+    bid.token_account = ctx.accounts.bid_token_account.key();
+    bid.refund_account = None;
+    bid.bump = bid_bump;
+    bid.escrowed_fractions = 0;
+    bid.fraction_credit = 0;
+    bid.fraction_account = ctx.accounts.fraction_account.key();
+    bid.escrow_bump = escrow_bump;
+
+    // Ending straight away, so the usual end, claim and redeem instructions settle it
+    auction.start_timestamp = current_timestamp;
+    auction.end_timestamp = current_timestamp;
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.top_bid = offer_amount;
+    auction.top_bidder = ctx.accounts.bidder.key();
+    auction.reserve_price = reserve_price;
+    auction.settings = settings.key();
+    auction.vault = vault.key();
+    auction.payment_treasury = ctx.accounts.payment_treasury.key();
+    auction.bump = auction_bump;
+    auction.lister = ctx.accounts.bidder.key();
+    auction.combined = true;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.accepted = true;
+
+    Ok(())
+}
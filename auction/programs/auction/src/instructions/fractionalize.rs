@@ -0,0 +1,316 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{self, system_instruction};
+use anchor_spl::token::{Token, Mint, TokenAccount};
+
+use crate::errors::*;
+use crate::state::{Authority, Curation, FractionalizeBumps};
+use crate::vault::{MAX_VAULT_SIZE, token_vault_program};
+use crate::vault_instructions::{
+    self,
+    InitVault,
+    AddTokenToInactiveVault,
+    ActivateVault,
+    WithdrawSharesFromTreasury
+};
+use crate::price_tracker::{self, InitializePriceAccount, VoteSettings};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+#[instruction(bumps: FractionalizeBumps, number_of_fractions: u64, decimals: u8)]
+pub struct Fractionalize<'info> {
+
+    // The program authority is made the authority of the new vault
+    // and signs every invocation to the vault program after its creation.
+    #[account(
+        seeds = [b"authority".as_ref(), crate::id().as_ref()],
+        bump = authority.bump
+    )]
+    pub authority: Box<Account<'info, Authority>>,
+
+    #[account(
+        init,
+        payer = curator,
+        space = 8 + Curation::space(),
+        seeds = [b"curation".as_ref(), vault.key().as_ref()],
+        bump = bumps.curation,
+    )]
+    pub curation: Box<Account<'info, Curation>>,
+
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    // Created by this instruction and initialised by the vault program.
+    #[account(mut)]
+    pub vault: Signer<'info>,
+
+    // -------------------------------------------
+    // The vault mint and treasuries, all of which
+    // are under the control of the vault PDA:
+    // -------------------------------------------
+
+    // The decimals are only needed here, the price tracker reads them off the mint.
+    #[account(
+        init,
+        payer = curator,
+        seeds = [b"fraction_mint".as_ref(), vault.key().as_ref()],
+        bump = bumps.fraction_mint,
+        mint::decimals = decimals,
+        mint::authority = vault_pda,
+    )]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = curator,
+        seeds = [b"fraction_treasury".as_ref(), vault.key().as_ref()],
+        bump = bumps.fraction_treasury,
+        token::mint = fraction_mint,
+        token::authority = vault_pda,
+    )]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = curator,
+        seeds = [b"redeem_treasury".as_ref(), vault.key().as_ref()],
+        bump = bumps.redeem_treasury,
+        token::mint = payment_mint,
+        token::authority = vault_pda,
+    )]
+    pub redeem_treasury: Box<Account<'info, TokenAccount>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // Receives every fraction minted by the vault.
+    #[account(
+        init,
+        payer = curator,
+        seeds = [b"fractions".as_ref(), vault.key().as_ref(), curator.key().as_ref()],
+        bump = bumps.curator_fractions,
+        token::mint = fraction_mint,
+        token::authority = curator,
+    )]
+    pub curator_fraction_account: Box<Account<'info, TokenAccount>>,
+
+    // ------------------------------------
+    // The NFT and its safety deposit box:
+    // ------------------------------------
+
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = curator_nft_account.mint == nft_mint.key(),
+        constraint = curator_nft_account.owner == curator.key(),
+    )]
+    pub curator_nft_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = curator,
+        seeds = [b"store".as_ref(), vault.key().as_ref(), nft_mint.key().as_ref()],
+        bump = bumps.store,
+        token::mint = nft_mint,
+        token::authority = vault_pda,
+    )]
+    pub store: Box<Account<'info, TokenAccount>>,
+
+    // Created by the vault program, which also checks its address.
+    #[account(mut)]
+    pub safety_deposit_box: AccountInfo<'info>,
+
+    // ------------------------------------------------------
+    // Accounts needed for the price account initialisation:
+    // ------------------------------------------------------
+
+    #[account(mut)]
+    pub external_pricing_account: Signer<'info>,
+
+    #[account(mut)]
+    pub price_store: Signer<'info>,
+
+    #[account(mut)]
+    pub price_store_authority: AccountInfo<'info>,
+
+    pub locked_mint: Box<Account<'info, Mint>>,
+
+    pub price_program: AccountInfo<'info>,
+
+    // ---------------------------------------------
+    // Additional accounts for the vault invocations:
+    // ---------------------------------------------
+
+    pub vault_pda: AccountInfo<'info>,
+
+    pub vault_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> Fractionalize<'info> {
+
+    fn create_vault_account(&self) -> ProgramResult {
+        solana_program::program::invoke(
+            &system_instruction::create_account(
+                &self.curator.key(),
+                &self.vault.key(),
+                self.rent.minimum_balance(MAX_VAULT_SIZE),
+                MAX_VAULT_SIZE as u64,
+                &token_vault_program::ID
+            ),
+            &[
+                self.curator.to_account_info(),
+                self.vault.to_account_info(),
+                self.system_program.to_account_info(),
+            ]
+        )
+    }
+
+    // The fractions in the vault's fraction treasury are not in circulation, so they are left out of the quorum.
+    fn initialize_price_account(
+        &self,
+        price_pda_bump: u8,
+        min_price: u64,
+        settings: VoteSettings
+    ) -> ProgramResult {
+        price_tracker::initialize_price_account(
+            CpiContext::new(
+                self.price_program.clone(),
+                InitializePriceAccount {
+                    price_account: self.external_pricing_account.to_account_info(),
+                    store: self.price_store.to_account_info(),
+                    store_authority_pda: self.price_store_authority.clone(),
+                    store_mint: self.fraction_mint.to_account_info(),
+                    redeem_mint: self.payment_mint.to_account_info(),
+                    locked_mint: self.locked_mint.to_account_info(),
+                    vault: self.vault.to_account_info(),
+                    signer: self.curator.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                }
+            ).with_remaining_accounts(vec![self.fraction_treasury.to_account_info()]),
+            price_pda_bump,
+            min_price,
+            settings
+        )
+    }
+
+    fn init_vault(&self) -> ProgramResult {
+        vault_instructions::init_vault(
+            CpiContext::new(
+                self.vault_program.clone(),
+                InitVault {
+                    fraction_mint: self.fraction_mint.to_account_info(),
+                    redeem_treasury: self.redeem_treasury.to_account_info(),
+                    fraction_treasury: self.fraction_treasury.to_account_info(),
+                    vault: self.vault.to_account_info(),
+                    vault_authority: self.authority.to_account_info(),
+                    external_pricing_account: self.external_pricing_account.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                }
+            ),
+            false
+        )
+    }
+
+    fn add_nft_to_vault(&self) -> ProgramResult {
+        vault_instructions::add_token_to_inactive_vault(
+            CpiContext::new(
+                self.vault_program.clone(),
+                AddTokenToInactiveVault {
+                    safety_deposit_box: self.safety_deposit_box.clone(),
+                    token_account: self.curator_nft_account.to_account_info(),
+                    store: self.store.to_account_info(),
+                    vault: self.vault.to_account_info(),
+                    vault_authority: self.authority.to_account_info(),
+                    payer: self.curator.to_account_info(),
+                    transfer_authority: self.curator.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                }
+            ).with_signer(&[&[b"authority".as_ref(), crate::id().as_ref(), &[self.authority.bump]]]),
+            self.curator_nft_account.amount
+        )
+    }
+
+    fn activate_vault(&self, number_of_fractions: u64) -> ProgramResult {
+        vault_instructions::activate_vault(
+            CpiContext::new(
+                self.vault_program.clone(),
+                ActivateVault {
+                    vault: self.vault.to_account_info(),
+                    fraction_mint: self.fraction_mint.to_account_info(),
+                    fraction_treasury: self.fraction_treasury.to_account_info(),
+                    vault_pda: self.vault_pda.clone(),
+                    vault_authority: self.authority.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                }
+            ).with_signer(&[&[b"authority".as_ref(), crate::id().as_ref(), &[self.authority.bump]]]),
+            number_of_fractions
+        )
+    }
+
+    fn withdraw_fractions_to_curator(&self, number_of_fractions: u64) -> ProgramResult {
+        vault_instructions::withdraw_shares_from_treasury(
+            CpiContext::new(
+                self.vault_program.clone(),
+                WithdrawSharesFromTreasury {
+                    destination: self.curator_fraction_account.to_account_info(),
+                    fraction_treasury: self.fraction_treasury.to_account_info(),
+                    vault: self.vault.to_account_info(),
+                    vault_pda: self.vault_pda.clone(),
+                    vault_authority: self.authority.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                }
+            ).with_signer(&[&[b"authority".as_ref(), crate::id().as_ref(), &[self.authority.bump]]]),
+            number_of_fractions
+        )
+    }
+
+}
+
+pub fn handler(
+    ctx: Context<Fractionalize>,
+    bumps: FractionalizeBumps,
+    number_of_fractions: u64,
+    min_price: u64,
+    settings: VoteSettings
+) -> ProgramResult {
+    let vault = ctx.accounts.vault.key();
+
+    // Make sure every invocation goes to the genuine programs
+    utils::assert_vault_program(&ctx.accounts.vault_program)?;
+    utils::assert_vault_pda(&vault, &ctx.accounts.vault_pda)?;
+    utils::assert_price_program(&ctx.accounts.price_program)?;
+    if number_of_fractions == 0 {
+        return Err(AuctionError::InvalidNumberOfFractions.into());
+    }
+
+    // Create the price account which will decide when the vault may be combined
+    ctx.accounts.initialize_price_account(bumps.price_pda, min_price, settings)?;
+
+    // Create the vault with the program authority as its authority from the outset,
+    // lock the NFT inside it and hand all of the fractions over to the curator.
+    ctx.accounts.create_vault_account()?;
+    ctx.accounts.init_vault()?;
+    ctx.accounts.add_nft_to_vault()?;
+    ctx.accounts.activate_vault(number_of_fractions)?;
+    ctx.accounts.withdraw_fractions_to_curator(number_of_fractions)?;
+
+    let curation = &mut ctx.accounts.curation;
+    curation.curator = ctx.accounts.curator.key();
+    curation.vault = vault;
+    curation.external_pricing_account = ctx.accounts.external_pricing_account.key();
+    curation.bump = bumps.curation;
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::state::{Authority};
+use crate::vault::price_tracker_program;
 
 #[derive(Accounts)]
 #[instruction(authority_bump: u8)]
@@ -27,5 +28,6 @@ pub fn handler(ctx: Context<Init>, authority_bump: u8) -> ProgramResult {
     let authority = &mut ctx.accounts.authority;
     authority.owner = ctx.accounts.signer.key();
     authority.bump = authority_bump;
+    authority.trusted_price_programs = vec![price_tracker_program::ID];
     Ok(())
 }
\ No newline at end of file
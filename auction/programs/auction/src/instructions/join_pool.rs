@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, Contribution};
+
+#[derive(Accounts)]
+#[instruction(contribution_bump: u8)]
+pub struct JoinPool<'info> {
+
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + Contribution::space(),
+        seeds = [b"contribution".as_ref(), pool.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution_bump,
+    )]
+    pub contribution: Box<Account<'info, Contribution>>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+pub fn handler(ctx: Context<JoinPool>, contribution_bump: u8) -> ProgramResult {
+    let contribution = &mut ctx.accounts.contribution;
+    contribution.pool = ctx.accounts.pool.key();
+    contribution.contributor = ctx.accounts.contributor.key();
+    contribution.amount = 0;
+    contribution.bump = contribution_bump;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Mint, TokenAccount};
+
+use crate::state::{Auction, Authority, Settings};
+use crate::vault::Vault;
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+#[instruction(auction_bump: u8)]
+pub struct List<'info> {
+
+    #[account(
+        seeds = [b"authority".as_ref(), crate::id().as_ref()],
+        bump = authority.bump
+    )]
+    pub authority: Account<'info, Authority>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Auction::space(),
+        seeds = [b"auction".as_ref(), vault.key().as_ref()],
+        bump = auction_bump,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    pub settings: Box<Account<'info, Settings>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = payment_mint,
+        token::authority = auction
+    )]
+    pub payment_treasury: Box<Account<'info, TokenAccount>>,
+
+    pub vault: Box<Account<'info, Vault>>,
+
+    // Deserialised in the handler once its owner is known to be a trusted price program.
+    pub external_pricing_account: AccountInfo<'info>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // Pays the rent for the new accounts, which is refunded if the listing expires.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+pub fn handler(ctx: Context<List>, auction_bump: u8) -> ProgramResult {
+    let vault = &ctx.accounts.vault;
+    let authority = &ctx.accounts.authority;
+    let external_pricing_account = utils::load_external_pricing_account(authority, &ctx.accounts.external_pricing_account)?;
+
+    // The same checks as when starting an auction, short of combining the vault
+    utils::assert_vault_owned_by_auction_program_authority(vault, authority)?;
+    utils::assert_correct_pricing_account(vault, &ctx.accounts.external_pricing_account)?;
+    utils::assert_payment_mint_matches_price_mint(&external_pricing_account, &ctx.accounts.payment_mint.key())?;
+    utils::assert_vault_allowed_to_combine(&external_pricing_account)?;
+    utils::assert_vault_in_active_state(vault)?;
+
+    let settings = &ctx.accounts.settings;
+    let auction = &mut ctx.accounts.auction;
+    let current_timestamp = utils::get_current_timestamp()?;
+
+    // Nobody holds the top bid until the first bid, placed through start, combines the vault
+    auction.start_timestamp = current_timestamp;
+    auction.end_timestamp = current_timestamp + settings.duration;
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.top_bid = 0;
+    auction.top_bidder = Pubkey::default();
+    auction.reserve_price = 0;
+    auction.settings = settings.key();
+    auction.vault = vault.key();
+    auction.payment_treasury = ctx.accounts.payment_treasury.key();
+    auction.bump = auction_bump;
+    auction.lister = ctx.accounts.payer.key();
+    auction.combined = false;
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+
+use crate::errors::*;
+use crate::state::{Offer, Authority, Settings};
+use crate::vault::Vault;
+use crate::native;
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+#[instruction(offer_bump: u8, authority_bump: u8)]
+pub struct MakeOffer<'info> {
+
+    #[account(
+        seeds = [b"authority".as_ref(), crate::id().as_ref()],
+        bump = authority.bump
+    )]
+    pub authority: Account<'info, Authority>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + Offer::space(),
+        seeds = [b"offer".as_ref(), vault.key().as_ref(), bidder.key().as_ref()],
+        bump = offer_bump,
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    // Holds no data, it only signs for the offer: over its token accounts
+    // and to pay for the combination.
+    #[account(
+        seeds = [b"offer_authority".as_ref(), offer.key().as_ref()],
+        bump = authority_bump
+    )]
+    pub offer_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = bidder,
+        token::mint = payment_mint,
+        token::authority = offer_authority
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = bidder,
+        token::mint = fraction_mint,
+        token::authority = offer_authority
+    )]
+    pub fraction_account: Box<Account<'info, TokenAccount>>,
+
+    // The settings the auction is settled with once the offer is accepted.
+    pub settings: Box<Account<'info, Settings>>,
+
+    #[account(has_one = fraction_mint)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    // Deserialised in the handler once its owner is known to be a trusted price program.
+    pub external_pricing_account: AccountInfo<'info>,
+
+    // Either a token account of the payment mint owned by the bidder or,
+    // when offering native SOL, the bidder's wallet.
+    #[account(mut)]
+    pub paying_token_account: AccountInfo<'info>,
+
+    // Pays the rent for the new accounts.
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> MakeOffer<'info> {
+
+    fn transfer_to_escrow(&self, amount: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.paying_token_account.clone(),
+                    to: self.escrow.to_account_info(),
+                    authority: self.bidder.to_account_info()
+                }
+            ),
+            amount
+        )?;
+        Ok(())
+    }
+
+    fn wrap_into_escrow(&self, amount: u64) -> ProgramResult {
+        native::wrap(
+            &self.bidder.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.system_program.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount
+        )
+    }
+
+}
+
+pub fn handler(
+    ctx: Context<MakeOffer>,
+    offer_bump: u8,
+    authority_bump: u8,
+    amount: u64,
+    expiry: u64
+) -> ProgramResult {
+    let vault = &ctx.accounts.vault;
+    let authority = &ctx.accounts.authority;
+    let payment_mint = ctx.accounts.payment_mint.key();
+    let bidder = ctx.accounts.bidder.key();
+    let external_pricing_account = utils::load_external_pricing_account(authority, &ctx.accounts.external_pricing_account)?;
+
+    // The price tracker prices the vault at the offer once it is accepted, so the vault must be priced by it
+    utils::assert_vault_owned_by_auction_program_authority(vault, authority)?;
+    utils::assert_correct_pricing_account(vault, &ctx.accounts.external_pricing_account)?;
+    utils::assert_price_tracked(&ctx.accounts.external_pricing_account)?;
+    utils::assert_payment_mint_matches_price_mint(&external_pricing_account, &payment_mint)?;
+    utils::assert_vault_in_active_state(vault)?;
+
+    if expiry <= utils::get_current_timestamp()? {
+        return Err(AuctionError::OfferHasExpired.into());
+    }
+
+    if native::is_native_payment(&payment_mint, &ctx.accounts.paying_token_account, &bidder) {
+        ctx.accounts.wrap_into_escrow(amount)?;
+    } else {
+        let paying_token_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.paying_token_account)?;
+        utils::assert_token_account_mint(&paying_token_account, &payment_mint)?;
+        utils::assert_sufficient_funds(&paying_token_account, amount)?;
+        ctx.accounts.transfer_to_escrow(amount)?;
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.vault = vault.key();
+    offer.bidder = bidder;
+    offer.settings = ctx.accounts.settings.key();
+    offer.payment_mint = payment_mint;
+    offer.escrow = ctx.accounts.escrow.key();
+    offer.fraction_account = ctx.accounts.fraction_account.key();
+    offer.amount = amount;
+    offer.expiry = expiry;
+    offer.votes = 0;
+    offer.accepted = false;
+    offer.refunded = false;
+    offer.bump = offer_bump;
+    offer.authority_bump = authority_bump;
+
+    Ok(())
+}
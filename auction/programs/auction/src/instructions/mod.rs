@@ -1,21 +1,57 @@
 pub mod init;
 pub mod set_owner;
+pub mod add_price_program;
+pub mod remove_price_program;
 pub mod create_settings;
+pub mod fractionalize;
+pub mod release_vault;
 pub mod start;
+pub mod list;
+pub mod close_listing;
 pub mod place_bid;
 pub mod withdraw_bid;
+pub mod withdraw_bids;
 pub mod end;
 pub mod claim;
+pub mod claim_all;
 pub mod redeem;
+pub mod create_pool;
+pub mod join_pool;
+pub mod contribute;
+pub mod pool_bid;
+pub mod withdraw_contribution;
+pub mod pool_claim;
+pub mod make_offer;
+pub mod accept_offer;
+pub mod withdraw_acceptance;
+pub mod execute_offer;
+pub mod refund_offer;
 pub mod utils;
 
 pub use init::*;
 pub use set_owner::*;
 pub use create_settings::*;
+pub use fractionalize::*;
+pub use release_vault::*;
 pub use start::*;
+pub use list::*;
+pub use close_listing::*;
 pub use place_bid::*;
 pub use withdraw_bid::*;
+pub use withdraw_bids::*;
 pub use end::*;
 pub use claim::*;
+pub use claim_all::*;
 pub use redeem::*;
+pub use create_pool::*;
+pub use join_pool::*;
+pub use contribute::*;
+pub use pool_bid::*;
+pub use withdraw_contribution::*;
+pub use pool_claim::*;
+pub use make_offer::*;
+pub use accept_offer::*;
+pub use withdraw_acceptance::*;
+pub use execute_offer::*;
+pub use refund_offer::*;
 pub use utils::*;
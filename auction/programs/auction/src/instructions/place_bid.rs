@@ -1,12 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount, CloseAccount};
+use std::result::Result;
 
+use crate::errors::*;
 use crate::state::{Auction, Bid, Settings};
 use crate::vault::Vault;
+use crate::native::{self, Unwrap};
 use crate::instructions::utils;
 
 #[derive(Accounts)]
-#[instruction(bid_bump: u8)]
+#[instruction(bid_bump: u8, escrow_bump: u8)]
 pub struct PlaceBid<'info> {
 
     #[account(
@@ -20,6 +24,7 @@ pub struct PlaceBid<'info> {
 
     pub settings: Box<Account<'info, Settings>>,
 
+    #[account(has_one = fraction_mint)]
     pub vault: Box<Account<'info, Vault>>,
 
     #[account(mut)]
@@ -36,35 +41,86 @@ pub struct PlaceBid<'info> {
     #[account(mut)]
     pub top_bid_token_account: Box<Account<'info, TokenAccount>>,
 
+    // The wallet of the current top bidder, which native SOL refunds are paid out to
+    // and which receives the rent of their bid accounts once they are closed.
+    #[account(mut, constraint = top_bidder.key() == auction.top_bidder)]
+    pub top_bidder: AccountInfo<'info>,
+
+    // The refund account registered by the current top bidder, if any.
+    #[account(mut)]
+    pub top_bid_refund_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow".as_ref(), top_bid.key().as_ref()],
+        bump = top_bid.escrow_bump
+    )]
+    pub top_bid_fraction_escrow: Box<Account<'info, TokenAccount>>,
+
+    // The account the current top bidder's escrowed fractions are returned to.
+    #[account(mut)]
+    pub top_bid_fraction_account: AccountInfo<'info>,
+
     #[account(
         init, 
-        payer = bidder, 
+        payer = payer, 
         space = 8 + Bid::space(),
-        seeds = [b"bid".as_ref(), bidder.key().as_ref(), auction.key().as_ref()],
+        seeds = [b"bid".as_ref(), beneficiary.key().as_ref(), auction.key().as_ref()],
         bump = bid_bump,
     )]
     pub bid: Box<Account<'info, Bid>>,
 
     #[account(
         init, 
-        payer = bidder, 
+        payer = payer, 
         token::mint = payment_mint,
         token::authority = bid
     )]
     pub bid_token_account: Box<Account<'info, TokenAccount>>,
 
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"escrow".as_ref(), bid.key().as_ref()],
+        bump = escrow_bump,
+        token::mint = fraction_mint,
+        token::authority = bid
+    )]
+    pub fraction_escrow: Box<Account<'info, TokenAccount>>,
+
     #[account(constraint = auction.payment_mint == payment_mint.key())]
     pub payment_mint: Box<Account<'info, Mint>>,
 
-    #[account(
-        mut,
-        constraint = paying_token_account.mint == payment_mint.key(),
-        constraint = paying_token_account.owner == bidder.key(),
-    )]
-    pub paying_token_account: Box<Account<'info, TokenAccount>>,
+    pub fraction_mint: Box<Account<'info, Mint>>,
 
+    // Left out of the fraction supply the escrowed fractions are credited against.
+    #[account(constraint = vault_fraction_treasury.key() == vault.fraction_treasury)]
+    pub vault_fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    // Either a token account of the payment mint which the funding authority may
+    // transfer from or, when bidding in native SOL, the funding authority's wallet.
+    #[account(mut)]
+    pub paying_token_account: AccountInfo<'info>,
+
+    // A token account of the fraction mint which the funding authority may transfer
+    // fractions to escrow from. Unused unless fractions are escrowed with the bid.
+    #[account(mut)]
+    pub paying_fraction_account: AccountInfo<'info>,
+
+    // Pays the rent for the new accounts, as well as for unwrapping native SOL refunds.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // The owner or a delegate of the paying token account.
     #[account(mut)]
-    pub bidder: Signer<'info>,
+    pub funding_authority: Signer<'info>,
+
+    // Recorded as the bidder, and so the one who can claim the auctioned assets.
+    pub beneficiary: AccountInfo<'info>,
+
+    // Only used to unwrap native SOL refunds.
+    #[account(mut)]
+    pub temporary_token_account: AccountInfo<'info>,
 
     pub token_program: Program<'info, Token>,
 
@@ -83,7 +139,7 @@ impl<'info> PlaceBid<'info> {
                 Transfer {
                     from: self.paying_token_account.to_account_info(),
                     to: self.payment_treasury.to_account_info(),
-                    authority: self.bidder.to_account_info()
+                    authority: self.funding_authority.to_account_info()
                 }
             ), 
             amount
@@ -91,55 +147,245 @@ impl<'info> PlaceBid<'info> {
         Ok(())
     }
 
-    fn transfer_back_to_top_bidder(&self) -> ProgramResult {
+    fn escrow_fractions(&self, fractions: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.paying_fraction_account.to_account_info(),
+                    to: self.fraction_escrow.to_account_info(),
+                    authority: self.funding_authority.to_account_info()
+                }
+            ),
+            fractions
+        )?;
+        Ok(())
+    }
+
+    fn wrap_into_treasury(&self, amount: u64) -> ProgramResult {
+        native::wrap(
+            &self.funding_authority.to_account_info(),
+            &self.payment_treasury.to_account_info(),
+            &self.system_program.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount
+        )
+    }
+
+    fn unwrap_back_to_top_bidder(&self) -> ProgramResult {
+        Unwrap {
+            temporary_token_account: self.temporary_token_account.clone(),
+            native_mint: self.payment_mint.to_account_info(),
+            payer: self.payer.to_account_info(),
+            destination: self.top_bidder.clone(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        }.unwrap(
+            &self.payment_treasury.to_account_info(),
+            &self.auction.to_account_info(),
+            &[&[b"auction".as_ref(), self.vault.key().as_ref(), &[self.auction.bump]]],
+            self.top_bid.amount - self.top_bid.fraction_credit
+        )
+    }
+
+    fn transfer_back_to_top_bidder(&self, to: &AccountInfo<'info>) -> ProgramResult {
         token::transfer(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(), 
                 Transfer {
                     from: self.payment_treasury.to_account_info(),
-                    to: self.top_bid_token_account.to_account_info(),
+                    to: to.clone(),
                     authority: self.auction.to_account_info()
                 },
                 &[&[b"auction".as_ref(), self.vault.key().as_ref(), &[self.auction.bump]]]
             ), 
-            self.top_bid.amount
+            self.top_bid.amount - self.top_bid.fraction_credit
         )?;
         Ok(())
     }
 
+    // Close the outbid bid token account, sweeping anything left in it to the refund account first.
+    fn close_top_bid_token_account(&self, refund_account: &AccountInfo<'info>) -> ProgramResult {
+        let seeds: &[&[&[u8]]] = &[&[b"bid".as_ref(), self.top_bid.bidder.as_ref(), self.top_bid.auction.as_ref(), &[self.top_bid.bump]]];
+        if !native::is_native_mint(&self.auction.payment_mint) && self.top_bid_token_account.amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.top_bid_token_account.to_account_info(),
+                        to: refund_account.clone(),
+                        authority: self.top_bid.to_account_info()
+                    },
+                    seeds
+                ),
+                self.top_bid_token_account.amount
+            )?;
+        }
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.top_bid_token_account.to_account_info(),
+                    destination: self.top_bidder.clone(),
+                    authority: self.top_bid.to_account_info()
+                },
+                seeds
+            )
+        )?;
+        Ok(())
+    }
+
+    // Return the outbid escrowed fractions and close the escrow.
+    fn close_top_bid_fraction_escrow(&self) -> ProgramResult {
+        let seeds: &[&[&[u8]]] = &[&[b"bid".as_ref(), self.top_bid.bidder.as_ref(), self.top_bid.auction.as_ref(), &[self.top_bid.bump]]];
+        if self.top_bid_fraction_escrow.amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.top_bid_fraction_escrow.to_account_info(),
+                        to: self.top_bid_fraction_account.clone(),
+                        authority: self.top_bid.to_account_info()
+                    },
+                    seeds
+                ),
+                self.top_bid_fraction_escrow.amount
+            )?;
+        }
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.top_bid_fraction_escrow.to_account_info(),
+                    destination: self.top_bidder.clone(),
+                    authority: self.top_bid.to_account_info()
+                },
+                seeds
+            )
+        )?;
+        Ok(())
+    }
+
+    // Whether the escrowed fractions, if any, can still be returned to the account registered for them.
+    fn can_return_fractions(&self) -> bool {
+        if self.top_bid_fraction_escrow.amount == 0 {
+            return true;
+        }
+        if *self.top_bid_fraction_account.key != self.top_bid.fraction_account {
+            return false;
+        }
+        match Account::<TokenAccount>::try_from(&self.top_bid_fraction_account) {
+            Ok(token_account) => token_account.mint == self.fraction_mint.key(),
+            Err(_) => false
+        }
+    }
+
+    // The registered refund account, provided it can still receive the payment mint.
+    // It has to be the one passed in, so that the next bidder cannot hold the refund back.
+    fn registered_refund_account(&self) -> Result<Option<AccountInfo<'info>>, ProgramError> {
+        let refund_account = match self.top_bid.refund_account {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        if *self.top_bid_refund_account.key != refund_account {
+            return Err(AuctionError::RefundAccountMismatch.into());
+        }
+        if native::is_native_payment(&self.auction.payment_mint, &self.top_bid_refund_account, &self.top_bid.bidder) {
+            return Ok(Some(self.top_bid_refund_account.clone()));
+        }
+        match Account::<TokenAccount>::try_from(&self.top_bid_refund_account) {
+            Ok(token_account) if token_account.mint == self.auction.payment_mint => Ok(Some(self.top_bid_refund_account.clone())),
+            _ => Ok(None),
+        }
+    }
+
 }
 
-pub fn handler(ctx: Context<PlaceBid>, bid_bump: u8, bid_amount: u64) -> ProgramResult {
+pub fn handler(
+    ctx: Context<PlaceBid>,
+    bid_bump: u8,
+    escrow_bump: u8,
+    bid_amount: u64,
+    fractions: u64,
+    refund_account: Option<Pubkey>
+) -> ProgramResult {
     let auction = &ctx.accounts.auction;
     let settings = &ctx.accounts.settings;
-    let paying_token_account = &ctx.accounts.paying_token_account;
+    let funding_authority = ctx.accounts.funding_authority.key();
+    let is_native = native::is_native_payment(&auction.payment_mint, &ctx.accounts.paying_token_account, &funding_authority);
 
-    utils::assert_sufficient_funds(paying_token_account, bid_amount)?;
     utils::assert_auction_has_not_ended(auction)?;
+    utils::assert_auction_combined(auction)?;
     utils::assert_sufficient_bid(bid_amount, auction.top_bid, settings.bid_increment)?;
 
-    // Transfer payment from bidder
-    ctx.accounts.transfer_to_treasury(bid_amount)?;
+    // Escrow any fractions the bidder holds, crediting them with those fractions' share of
+    // the bid, which they would otherwise only get back when redeeming.
+    if fractions > 0 {
+        let paying_fraction_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.paying_fraction_account)?;
+        utils::assert_token_account_mint(&paying_fraction_account, &ctx.accounts.fraction_mint.key())?;
+        utils::assert_sufficient_funds(&paying_fraction_account, fractions)?;
+        ctx.accounts.escrow_fractions(fractions)?;
+    }
+    let fraction_supply = utils::calculate_circulating_supply(ctx.accounts.fraction_mint.supply, &ctx.accounts.vault_fraction_treasury, 0)?;
+    let fraction_credit = utils::calculate_fraction_credit(bid_amount, fractions, fraction_supply)?;
+    let payment = bid_amount - fraction_credit;
+
+    // Transfer payment from the funding authority, wrapping it if they are paying in native SOL
+    if is_native {
+        ctx.accounts.wrap_into_treasury(payment)?;
+    } else {
+        let paying_token_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.paying_token_account)?;
+        utils::assert_token_account_mint(&paying_token_account, &auction.payment_mint)?;
+        utils::assert_sufficient_funds(&paying_token_account, payment)?;
+        // The token program makes sure the funding authority is the owner or a delegate
+        ctx.accounts.transfer_to_treasury(payment)?;
+    }
 
     let current_timestamp = utils::get_current_timestamp()?;
     let bid = &mut ctx.accounts.bid;
-    bid.bidder = ctx.accounts.bidder.key();
+    bid.bidder = ctx.accounts.beneficiary.key();
     bid.auction = auction.key();
     bid.amount = bid_amount;
     bid.timestamp = current_timestamp;
     bid.withdrawable = false;
     bid.token_account = ctx.accounts.bid_token_account.key();
+    bid.refund_account = refund_account;
     bid.bump = bid_bump;
+    bid.escrowed_fractions = fractions;
+    bid.fraction_credit = fraction_credit;
+    bid.fraction_account = ctx.accounts.paying_fraction_account.key();
+    bid.escrow_bump = escrow_bump;
 
-    // Transfer back the old bid
-    ctx.accounts.transfer_back_to_top_bidder()?;
+    // Refund the old bid straight away, either into the old bidder's wallet for native SOL
+    // or into the refund account they registered, return their escrowed fractions and close
+    // their bid accounts. Otherwise the refund is held in their bid token account, and the
+    // fractions in their escrow, until they withdraw them.
+    let top_bid = &ctx.accounts.top_bid;
+    let refund_to_wallet = native::is_native_mint(&auction.payment_mint)
+        && top_bid.refund_account.unwrap_or(top_bid.bidder) == top_bid.bidder;
+    let can_return_fractions = ctx.accounts.can_return_fractions();
+    let registered_refund_account = ctx.accounts.registered_refund_account()?.filter(|_| can_return_fractions);
+    if refund_to_wallet && can_return_fractions {
+        ctx.accounts.unwrap_back_to_top_bidder()?;
+        let top_bidder = ctx.accounts.top_bidder.clone();
+        ctx.accounts.close_top_bid_token_account(&top_bidder)?;
+        ctx.accounts.close_top_bid_fraction_escrow()?;
+        close_top_bid(&mut ctx.accounts.top_bid, top_bidder)?;
+    } else if let (false, Some(refund_account)) = (refund_to_wallet, registered_refund_account) {
+        ctx.accounts.transfer_back_to_top_bidder(&refund_account)?;
+        ctx.accounts.close_top_bid_token_account(&refund_account)?;
+        ctx.accounts.close_top_bid_fraction_escrow()?;
+        close_top_bid(&mut ctx.accounts.top_bid, ctx.accounts.top_bidder.clone())?;
+    } else {
+        let top_bid_token_account = ctx.accounts.top_bid_token_account.to_account_info();
+        ctx.accounts.transfer_back_to_top_bidder(&top_bid_token_account)?;
+        ctx.accounts.top_bid.withdrawable = true;
+    }
 
-    let top_bid = &mut ctx.accounts.top_bid;
     let auction = &mut ctx.accounts.auction;
-
-    top_bid.withdrawable = true;
     auction.top_bid = bid_amount;
-    auction.top_bidder = ctx.accounts.bidder.key();
+    auction.top_bidder = ctx.accounts.beneficiary.key();
 
     // Extend the auction if needed
     let time_remaining = auction.end_timestamp - current_timestamp;
@@ -148,4 +394,11 @@ pub fn handler(ctx: Context<PlaceBid>, bid_bump: u8, bid_amount: u64) -> Program
     }
     
     Ok(())
+}
+
+// The bid is zeroed out before closing as it is still written back when the instruction exits.
+fn close_top_bid<'info>(top_bid: &mut Account<'info, Bid>, top_bidder: AccountInfo<'info>) -> ProgramResult {
+    top_bid.amount = 0;
+    top_bid.withdrawable = false;
+    top_bid.close(top_bidder)
 }
\ No newline at end of file
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{self, instruction::Instruction};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::{Token, Mint, TokenAccount};
+
+use crate::state::Pool;
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct PoolBid<'info> {
+
+    // The pool is both the funding authority and the beneficiary of its bids,
+    // and receives the rent of its bid accounts once they are outbid.
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), auction.key().as_ref()],
+        bump = pool.bump,
+        has_one = auction,
+        constraint = pool.token_account == pool_token_account.key()
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    // Pays for the bid, and is also registered as the bid's refund account
+    // so that outbid funds go straight back to the pool, closing its bid
+    // accounts so that it can bid again.
+    #[account(mut)]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    // ---------------------------------------------------
+    // Accounts passed through to the place bid invocation:
+    // ---------------------------------------------------
+
+    // Left raw, as the place bid invocation updates it and it must not be written back here.
+    #[account(mut)]
+    pub auction: AccountInfo<'info>,
+
+    pub settings: AccountInfo<'info>,
+
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payment_treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub top_bid: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub top_bid_token_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub top_bidder: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub top_bid_refund_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub top_bid_fraction_escrow: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub top_bid_fraction_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub bid: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub bid_token_account: Signer<'info>,
+
+    // The pool has no fractions to escrow, but every bid gets an escrow.
+    #[account(mut)]
+    pub fraction_escrow: AccountInfo<'info>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    pub fraction_mint: AccountInfo<'info>,
+
+    pub vault_fraction_treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub temporary_token_account: AccountInfo<'info>,
+
+    pub auction_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> PoolBid<'info> {
+
+    // Bid through the program's own place bid instruction, signing for the pool.
+    fn place_bid(&self, bid_bump: u8, escrow_bump: u8, bid_amount: u64) -> ProgramResult {
+        let instruction = Instruction {
+            program_id: crate::id(),
+            accounts: crate::accounts::PlaceBid {
+                auction: self.auction.key(),
+                settings: self.settings.key(),
+                vault: self.vault.key(),
+                payment_treasury: self.payment_treasury.key(),
+                top_bid: self.top_bid.key(),
+                top_bid_token_account: self.top_bid_token_account.key(),
+                top_bidder: self.top_bidder.key(),
+                top_bid_refund_account: self.top_bid_refund_account.key(),
+                top_bid_fraction_escrow: self.top_bid_fraction_escrow.key(),
+                top_bid_fraction_account: self.top_bid_fraction_account.key(),
+                bid: self.bid.key(),
+                bid_token_account: self.bid_token_account.key(),
+                fraction_escrow: self.fraction_escrow.key(),
+                payment_mint: self.payment_mint.key(),
+                fraction_mint: self.fraction_mint.key(),
+                vault_fraction_treasury: self.vault_fraction_treasury.key(),
+                paying_token_account: self.pool_token_account.key(),
+                paying_fraction_account: self.pool.key(),
+                payer: self.payer.key(),
+                funding_authority: self.pool.key(),
+                beneficiary: self.pool.key(),
+                temporary_token_account: self.temporary_token_account.key(),
+                token_program: self.token_program.key(),
+                system_program: self.system_program.key(),
+                rent: self.rent.key(),
+            }.to_account_metas(None),
+            data: crate::instruction::PlaceBid {
+                bid_bump,
+                escrow_bump,
+                bid_amount,
+                fractions: 0,
+                refund_account: Some(self.pool_token_account.key()),
+            }.data(),
+        };
+        solana_program::program::invoke_signed(
+            &instruction,
+            &[
+                self.auction.clone(),
+                self.settings.clone(),
+                self.vault.clone(),
+                self.payment_treasury.clone(),
+                self.top_bid.clone(),
+                self.top_bid_token_account.clone(),
+                self.top_bidder.clone(),
+                self.top_bid_refund_account.clone(),
+                self.top_bid_fraction_escrow.clone(),
+                self.top_bid_fraction_account.clone(),
+                self.bid.clone(),
+                self.bid_token_account.to_account_info(),
+                self.fraction_escrow.clone(),
+                self.payment_mint.to_account_info(),
+                self.fraction_mint.clone(),
+                self.vault_fraction_treasury.clone(),
+                self.pool_token_account.to_account_info(),
+                self.payer.to_account_info(),
+                self.pool.to_account_info(),
+                self.temporary_token_account.clone(),
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+                self.rent.to_account_info(),
+                self.auction_program.clone(),
+            ],
+            &[&[b"pool".as_ref(), self.auction.key().as_ref(), &[self.pool.bump]]]
+        )
+    }
+
+}
+
+pub fn handler(ctx: Context<PoolBid>, bid_bump: u8, escrow_bump: u8, bid_amount: u64) -> ProgramResult {
+    // Everything else is checked by the place bid instruction itself
+    utils::assert_auction_program(&ctx.accounts.auction_program)?;
+    utils::assert_sufficient_funds(&ctx.accounts.pool_token_account, bid_amount)?;
+
+    ctx.accounts.place_bid(bid_bump, escrow_bump, bid_amount)
+}
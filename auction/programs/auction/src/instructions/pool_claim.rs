@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{self, instruction::Instruction};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::Pool;
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct PoolClaim<'info> {
+
+    #[account(
+        seeds = [b"pool".as_ref(), auction.key().as_ref()],
+        bump = pool.bump,
+        has_one = auction
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    // Must be owned by the pool, which keeps the won assets on behalf of its contributors.
+    #[account(mut, constraint = destination_token_account.owner == pool.key())]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+
+    // ---------------------------------------------
+    // Accounts passed through to the claim invocation:
+    // ---------------------------------------------
+
+    #[account(mut)]
+    pub authority: AccountInfo<'info>,
+
+    // Left raw, as the claim invocation updates it and it must not be written back here.
+    #[account(mut)]
+    pub auction: AccountInfo<'info>,
+
+    pub settings: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub locked_token_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub safety_deposit_box: AccountInfo<'info>,
+
+    pub vault_pda: AccountInfo<'info>,
+
+    pub vault_program: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub fraction_mint: AccountInfo<'info>,
+
+    pub auction_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> PoolClaim<'info> {
+
+    // Claim through the program's own claim instruction, signing for the pool.
+    fn claim(&self, amount: u64) -> ProgramResult {
+        let instruction = Instruction {
+            program_id: crate::id(),
+            accounts: crate::accounts::Claim {
+                authority: self.authority.key(),
+                auction: self.auction.key(),
+                settings: self.settings.key(),
+                vault: self.vault.key(),
+                destination_token_account: self.destination_token_account.key(),
+                beneficiary: self.pool.key(),
+                token_program: self.token_program.key(),
+                locked_token_account: self.locked_token_account.key(),
+                safety_deposit_box: self.safety_deposit_box.key(),
+                vault_pda: self.vault_pda.key(),
+                vault_program: self.vault_program.key(),
+                fraction_mint: self.fraction_mint.key(),
+                rent: self.rent.key(),
+            }.to_account_metas(None),
+            data: crate::instruction::Claim { amount }.data(),
+        };
+        solana_program::program::invoke_signed(
+            &instruction,
+            &[
+                self.authority.clone(),
+                self.auction.clone(),
+                self.settings.clone(),
+                self.vault.clone(),
+                self.destination_token_account.to_account_info(),
+                self.pool.to_account_info(),
+                self.token_program.to_account_info(),
+                self.locked_token_account.clone(),
+                self.safety_deposit_box.clone(),
+                self.vault_pda.clone(),
+                self.vault_program.clone(),
+                self.fraction_mint.clone(),
+                self.rent.to_account_info(),
+                self.auction_program.clone(),
+            ],
+            &[&[b"pool".as_ref(), self.auction.key().as_ref(), &[self.pool.bump]]]
+        )
+    }
+
+}
+
+pub fn handler(ctx: Context<PoolClaim>, amount: u64) -> ProgramResult {
+    // Everything else is checked by the claim instruction itself
+    utils::assert_auction_program(&ctx.accounts.auction_program)?;
+
+    ctx.accounts.claim(amount)
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Transfer, Mint, TokenAccount, CloseAccount};
-use anchor_lang::solana_program;
 
 use crate::state::{Auction, Settings};
-use crate::vault::{Vault, create_redeem_shares_instruction};
+use crate::vault::Vault;
+use crate::vault_instructions::{self, RedeemShares};
+use crate::native::{self, Unwrap};
 use crate::instructions::utils;
 
 #[derive(Accounts)]
@@ -38,19 +39,26 @@ pub struct Redeem<'info> {
     )]
     pub source_token_account: Box<Account<'info, TokenAccount>>,
 
-    #[account(
-        mut,
-        constraint = destination_token_account.mint == payment_treasury.mint
-    )]
-    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+    // Either a token account of the payment mint or, for native SOL auctions, 
+    // the redeemer's own wallet.
+    #[account(mut)]
+    pub destination_token_account: AccountInfo<'info>,
 
     #[account(mut)]
     pub fraction_mint: Box<Account<'info, Mint>>,
 
+    #[account(constraint = auction.payment_mint == payment_mint.key())]
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // Only used to unwrap native SOL proceeds.
+    #[account(mut)]
+    pub temporary_token_account: AccountInfo<'info>,
+
     pub vault_pda: AccountInfo<'info>,
 
     pub vault_program: AccountInfo<'info>,
 
+    #[account(mut)]
     pub redeemer: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
@@ -63,30 +71,42 @@ pub struct Redeem<'info> {
 
 impl<'info> Redeem<'info> {
 
-    fn redeem_context(&self) -> CpiContext<'_, '_, '_, 'info, RedeemShares<'info>> {
+    fn unwrap_accounts(&self) -> Unwrap<'info> {
+        Unwrap {
+            temporary_token_account: self.temporary_token_account.clone(),
+            native_mint: self.payment_mint.to_account_info(),
+            payer: self.redeemer.to_account_info(),
+            destination: self.redeemer.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        }
+    }
+
+    fn redeem_context(&self, proceeds_account: &AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, RedeemShares<'info>> {
         CpiContext::new(
             self.vault_program.clone(),
             RedeemShares {
-                vault: self.vault.to_account_info(),
-                vault_pda: self.vault_pda.to_account_info(),
-                src_account: self.source_token_account.to_account_info(),
-                dest_account: self.destination_token_account.to_account_info(),
+                outstanding_shares_account: self.source_token_account.to_account_info(),
+                proceeds_account: proceeds_account.clone(),
                 fraction_mint: self.fraction_mint.to_account_info(),
                 redeem_treasury: self.redeem_treasury.to_account_info(),
-                redeemer: self.redeemer.to_account_info(),
-                rent: self.rent.to_account_info(),
+                vault_pda: self.vault_pda.to_account_info(),
+                burn_authority: self.redeemer.to_account_info(),
+                vault: self.vault.to_account_info(),
                 token_program: self.token_program.to_account_info(),
+                rent: self.rent.to_account_info(),
             }
         )
     }
 
-    fn transfer_to_redeemer(&self, amount: u64) -> ProgramResult {
+    fn transfer_to_redeemer(&self, destination_token_account: &AccountInfo<'info>, amount: u64) -> ProgramResult {
         token::transfer(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(), 
                 Transfer {
                     from: self.payment_treasury.to_account_info(),
-                    to: self.destination_token_account.to_account_info(),
+                    to: destination_token_account.clone(),
                     authority: self.auction.to_account_info()
                 },
                 &[&[b"auction".as_ref(), self.vault.key().as_ref(), &[self.auction.bump]]]
@@ -113,61 +133,41 @@ impl<'info> Redeem<'info> {
 
 }
 
-#[derive(Accounts)]
-struct RedeemShares<'info> {
-    pub vault: AccountInfo<'info>,
-    pub vault_pda: AccountInfo<'info>,
-    pub src_account: AccountInfo<'info>,
-    pub dest_account: AccountInfo<'info>,
-    pub fraction_mint: AccountInfo<'info>,
-    pub redeem_treasury: AccountInfo<'info>,
-    pub redeemer: AccountInfo<'info>,
-    pub rent: AccountInfo<'info>,
-    pub token_program: AccountInfo<'info>
-}
-
-fn redeem_shares<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, RedeemShares<'info>>) -> ProgramResult {
-    let ix = create_redeem_shares_instruction(
-        Vault::owner(),
-        *ctx.accounts.src_account.key,
-        *ctx.accounts.dest_account.key,
-        *ctx.accounts.fraction_mint.key,
-        *ctx.accounts.redeem_treasury.key,
-        *ctx.accounts.vault_pda.key,
-        *ctx.accounts.redeemer.key,
-        *ctx.accounts.vault.key
-    );
-    solana_program::program::invoke(
-        &ix,
-        &[
-            ctx.accounts.src_account.clone(),
-            ctx.accounts.dest_account.clone(),
-            ctx.accounts.fraction_mint.clone(),
-            ctx.accounts.redeem_treasury.clone(),
-            ctx.accounts.vault_pda.clone(),
-            ctx.accounts.redeemer.clone(),
-            ctx.accounts.vault.clone(),
-            ctx.accounts.token_program.clone(),
-            ctx.accounts.rent.clone(),
-            ctx.program.clone(),
-        ]
-    )
-
-}
-
 pub fn handler(ctx: Context<Redeem>) -> ProgramResult {
 
     utils::assert_auction_has_ended(&ctx.accounts.auction)?;
     utils::assert_fee_delivered(&ctx.accounts.auction)?;
-
-    redeem_shares(ctx.accounts.redeem_context())?;
-
+    utils::assert_vault_program(&ctx.accounts.vault_program)?;
+    utils::assert_vault_pda(&ctx.accounts.vault.key(), &ctx.accounts.vault_pda)?;
+
+    // Native SOL proceeds are collected in a temporary token account which is
+    // then closed, unwrapping them into the redeemer's wallet.
+    let auction = &ctx.accounts.auction;
+    let is_native = native::is_native_payment(&auction.payment_mint, &ctx.accounts.destination_token_account, &ctx.accounts.redeemer.key());
+    let unwrap = ctx.accounts.unwrap_accounts();
+    let destination_token_account = if is_native {
+        unwrap.open()?;
+        unwrap.temporary_token_account.clone()
+    } else {
+        let destination_token_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.destination_token_account)?;
+        utils::assert_token_account_mint(&destination_token_account, &auction.payment_mint)?;
+        ctx.accounts.destination_token_account.clone()
+    };
+
+    vault_instructions::redeem_shares(ctx.accounts.redeem_context(&destination_token_account))?;
+
+    // The winner's escrowed fractions were already redeemed into the payment treasury on
+    // ending the auction, so the supply only counts fractions with a share of it left.
     let payment = utils::calculate_redeem_payment(
         ctx.accounts.payment_treasury.amount, 
         ctx.accounts.source_token_account.amount, 
         ctx.accounts.fraction_mint.supply
     )?;
-    ctx.accounts.transfer_to_redeemer(payment)?;
+    ctx.accounts.transfer_to_redeemer(&destination_token_account, payment)?;
+
+    if is_native {
+        unwrap.close()?;
+    }
 
     ctx.accounts.payment_treasury.reload()?;
     if ctx.accounts.payment_treasury.amount == 0 {
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount, CloseAccount};
+
+use crate::state::Offer;
+use crate::native::{self, Unwrap};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct RefundOffer<'info> {
+
+    #[account(
+        mut,
+        seeds = [b"offer".as_ref(), offer.vault.as_ref(), bidder.key().as_ref()],
+        bump = offer.bump,
+        has_one = bidder,
+        has_one = escrow,
+        has_one = fraction_account,
+        has_one = payment_mint
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    #[account(
+        seeds = [b"offer_authority".as_ref(), offer.key().as_ref()],
+        bump = offer.authority_bump
+    )]
+    pub offer_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub fraction_account: Box<Account<'info, TokenAccount>>,
+
+    // Either a token account of the payment mint or, for native SOL offers,
+    // the bidder's own wallet.
+    #[account(mut)]
+    pub to_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // Only used to unwrap native SOL refunds.
+    #[account(mut)]
+    pub temporary_token_account: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> RefundOffer<'info> {
+
+    fn transfer_refund(&self, amount: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.escrow.to_account_info(),
+                    to: self.to_account.clone(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            ),
+            amount
+        )?;
+        Ok(())
+    }
+
+    fn unwrap_refund(&self, amount: u64) -> ProgramResult {
+        Unwrap {
+            temporary_token_account: self.temporary_token_account.clone(),
+            native_mint: self.payment_mint.to_account_info(),
+            payer: self.bidder.to_account_info(),
+            destination: self.bidder.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        }.unwrap(
+            &self.escrow.to_account_info(),
+            &self.offer_authority,
+            &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]],
+            amount
+        )
+    }
+
+    fn close_escrow(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.escrow.to_account_info(),
+                    destination: self.bidder.to_account_info(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
+    fn close_fraction_account(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.fraction_account.to_account_info(),
+                    destination: self.bidder.to_account_info(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
+}
+
+pub fn handler(ctx: Context<RefundOffer>) -> ProgramResult {
+    let offer = &ctx.accounts.offer;
+
+    utils::assert_offer_has_expired(offer)?;
+    utils::assert_offer_not_accepted(offer)?;
+
+    let amount = ctx.accounts.escrow.amount;
+    if native::is_native_payment(&offer.payment_mint, &ctx.accounts.to_account, &offer.bidder) {
+        ctx.accounts.unwrap_refund(amount)?;
+    } else {
+        let to_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.to_account)?;
+        utils::assert_token_account_mint(&to_account, &offer.payment_mint)?;
+        ctx.accounts.transfer_refund(amount)?;
+    }
+    ctx.accounts.close_escrow()?;
+
+    // The offer is closed along with it, unless holders still have to withdraw their acceptances
+    if ctx.accounts.fraction_account.amount == 0 {
+        ctx.accounts.close_fraction_account()?;
+        ctx.accounts.offer.close(ctx.accounts.bidder.to_account_info())?;
+    } else {
+        ctx.accounts.offer.refunded = true;
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Authority, Curation};
+use crate::vault::Vault;
+use crate::vault_instructions::{self, SetVaultAuthority};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct ReleaseVault<'info> {
+
+    // The program authority is the current authority on the vault
+    // and as such it needs to sign the invocation handing it back.
+    #[account(
+        seeds = [b"authority".as_ref(), crate::id().as_ref()],
+        bump = authority.bump
+    )]
+    pub authority: Box<Account<'info, Authority>>,
+
+    // Closed once the vault has been released, refunding the curator.
+    #[account(
+        mut,
+        seeds = [b"curation".as_ref(), vault.key().as_ref()],
+        bump = curation.bump,
+        has_one = curator,
+        has_one = vault,
+        has_one = external_pricing_account,
+        close = curator
+    )]
+    pub curation: Box<Account<'info, Curation>>,
+
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    // Checked in the handler to be the vault's (uninitialised) auction PDA.
+    pub auction: AccountInfo<'info>,
+
+    // Deserialised in the handler once its owner is known to be a trusted price program.
+    pub external_pricing_account: AccountInfo<'info>,
+
+    pub vault_program: AccountInfo<'info>,
+
+}
+
+impl<'info> ReleaseVault<'info> {
+
+    fn set_vault_authority_context(&self) -> CpiContext<'_, '_, '_, 'info, SetVaultAuthority<'info>> {
+        CpiContext::new(
+            self.vault_program.clone(),
+            SetVaultAuthority {
+                vault: self.vault.to_account_info(),
+                vault_authority: self.authority.to_account_info(),
+                new_vault_authority: self.curator.to_account_info(),
+            }
+        )
+    }
+
+}
+
+pub fn handler(ctx: Context<ReleaseVault>) -> ProgramResult {
+    let vault = &ctx.accounts.vault;
+    let authority = &ctx.accounts.authority;
+    let external_pricing_account = utils::load_external_pricing_account(authority, &ctx.accounts.external_pricing_account)?;
+
+    // The vault can only be released while it has not been put up for auction
+    utils::assert_vault_program(&ctx.accounts.vault_program)?;
+    utils::assert_vault_owned_by_auction_program_authority(vault, authority)?;
+    utils::assert_correct_pricing_account(vault, &ctx.accounts.external_pricing_account)?;
+    utils::assert_vault_in_active_state(vault)?;
+    utils::assert_vault_not_allowed_to_combine(&external_pricing_account)?;
+    utils::assert_auction_not_started(&vault.key(), &ctx.accounts.auction)?;
+
+    // Hand the vault back to the curator
+    vault_instructions::set_vault_authority(
+        ctx.accounts
+            .set_vault_authority_context()
+            .with_signer(&[&[b"authority".as_ref(), crate::id().as_ref(), &[authority.bump]]])
+    )?;
+
+    Ok(())
+}
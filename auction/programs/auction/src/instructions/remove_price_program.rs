@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::instructions::set_owner::SetAuthority;
+
+pub fn handler(
+    ctx: Context<SetAuthority>,
+    price_program: Pubkey
+) -> ProgramResult {
+    let authority = &mut ctx.accounts.authority;
+
+    let index = match authority.trusted_price_programs.iter().position(|program| *program == price_program) {
+        Some(index) => index,
+        None => return Err(AuctionError::UntrustedPriceProgram.into()),
+    };
+
+    authority.trusted_price_programs.remove(index);
+    Ok(())
+}
@@ -3,12 +3,15 @@ use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
 use anchor_lang::solana_program;
 use std::result::Result;
 
+use crate::errors::*;
 use crate::state::{Auction, Bid, Authority, Settings};
-use crate::vault::{Vault, Price, create_combine_vault_instruction};
+use crate::vault::Vault;
+use crate::vault_instructions::{self, CombineVault};
+use crate::native;
 use crate::instructions::utils;
 
 #[derive(Accounts)]
-#[instruction(auction_bump: u8, bid_bump: u8)]
+#[instruction(auction_bump: u8, bid_bump: u8, escrow_bump: u8)]
 pub struct Start<'info> {
 
     // The program authority is the authority on the vault and as such
@@ -20,10 +23,11 @@ pub struct Start<'info> {
     )]
     pub authority: Account<'info, Authority>,
 
-    // The auction authority 
+    // The auction authority, which may already have been listed without any bids,
+    // in which case the opening bid takes over the listing.
     #[account(
-        init, 
-        payer = bidder,
+        init_if_needed,
+        payer = payer,
         space = 8 + Auction::space(),
         seeds = [b"auction".as_ref(), vault.key().as_ref()],
         bump = auction_bump,
@@ -32,9 +36,10 @@ pub struct Start<'info> {
 
     pub settings: Box<Account<'info, Settings>>,
 
+    // Created along with the auction, unless it is the treasury of a listing.
     #[account(
-        init, 
-        payer = bidder, 
+        init_if_needed,
+        payer = payer,
         token::mint = payment_mint,
         token::authority = auction
     )]
@@ -42,30 +47,51 @@ pub struct Start<'info> {
 
     #[account(
         init, 
-        payer = bidder, 
+        payer = payer, 
         space = 8 + Bid::space(),
-        seeds = [b"bid".as_ref(), bidder.key().as_ref(), auction.key().as_ref()],
+        seeds = [b"bid".as_ref(), beneficiary.key().as_ref(), auction.key().as_ref()],
         bump = bid_bump,
     )]
     pub bid: Box<Account<'info, Bid>>,
 
     #[account(
         init, 
-        payer = bidder,
+        payer = payer,
         token::mint = payment_mint, 
         token::authority = bid
     )]
     pub bid_token_account: Box<Account<'info, TokenAccount>>,
 
     #[account(
-        mut,
-        constraint = paying_token_account.mint == payment_mint.key(),
-        constraint = paying_token_account.owner == bidder.key(),
+        init,
+        payer = payer,
+        seeds = [b"escrow".as_ref(), bid.key().as_ref()],
+        bump = escrow_bump,
+        token::mint = fraction_mint,
+        token::authority = bid
     )]
-    pub paying_token_account: Box<Account<'info, TokenAccount>>,
+    pub fraction_escrow: Box<Account<'info, TokenAccount>>,
+
+    // Either a token account of the payment mint which the funding authority may
+    // transfer from or, when bidding in native SOL, the funding authority's wallet.
+    #[account(mut)]
+    pub paying_token_account: AccountInfo<'info>,
     
+    // A token account of the fraction mint which the funding authority may transfer
+    // fractions to escrow from. Unused unless fractions are escrowed with the bid.
+    #[account(mut)]
+    pub paying_fraction_account: AccountInfo<'info>,
+
+    // Pays the rent for the new accounts.
     #[account(mut)]
-    pub bidder: Signer<'info>,
+    pub payer: Signer<'info>,
+
+    // The owner or a delegate of the paying token account.
+    #[account(mut)]
+    pub funding_authority: Signer<'info>,
+
+    // Recorded as the bidder, and so the one who can claim the auctioned assets.
+    pub beneficiary: AccountInfo<'info>,
 
     // ------
     // Mints:
@@ -74,7 +100,6 @@ pub struct Start<'info> {
     #[account(mut)]
     pub fraction_mint: Box<Account<'info, Mint>>,
 
-    #[account(constraint = external_pricing_account.price_mint == payment_mint.key())]
     pub payment_mint: Box<Account<'info, Mint>>,
 
     // ------------------------------------------------------------
@@ -84,7 +109,8 @@ pub struct Start<'info> {
     #[account(mut)]
     pub vault: Box<Account<'info, Vault>>,
 
-    pub external_pricing_account: Box<Account<'info, Price>>,
+    // Deserialised in the handler once its owner is known to be a trusted price program.
+    pub external_pricing_account: AccountInfo<'info>,
 
     #[account(mut)]
     pub outstanding_fractions_token_account: Box<Account<'info, TokenAccount>>,
@@ -113,36 +139,74 @@ pub struct Start<'info> {
 
 impl<'info> Start<'info> {
 
-    fn combine_vault(&mut self) -> Result<u64, ProgramError> {
-        let balance_before = self.paying_token_account.amount;
-        combine_vault(
-            self.combine_context()
-                .with_signer(&[&[b"authority".as_ref(), crate::id().as_ref(), &[self.authority.bump]]])
+    // The auction signs as well, for when it pays for the combination out of the payment treasury.
+    fn combine_vault(
+        &self,
+        paying_token_account: &AccountInfo<'info>,
+        paying_transfer_authority: &AccountInfo<'info>,
+        auction_bump: u8
+    ) -> Result<u64, ProgramError> {
+        let balance_before = token::accessor::amount(paying_token_account)?;
+        vault_instructions::combine_vault(
+            self.combine_context(paying_token_account, paying_transfer_authority)
+                .with_signer(&[
+                    &[b"authority".as_ref(), crate::id().as_ref(), &[self.authority.bump]],
+                    &[b"auction".as_ref(), self.vault.key().as_ref(), &[auction_bump]]
+                ])
         )?;
-        self.paying_token_account.reload()?;
-        let balance_after = self.paying_token_account.amount;
+        let balance_after = token::accessor::amount(paying_token_account)?;
         Ok(balance_before - balance_after)
     }
 
-    fn combine_context(&self) -> CpiContext<'_, '_, '_, 'info, Combine<'info>> {
+    fn combine_context(
+        &self,
+        paying_token_account: &AccountInfo<'info>,
+        paying_transfer_authority: &AccountInfo<'info>
+    ) -> CpiContext<'_, '_, '_, 'info, CombineVault<'info>> {
         CpiContext::new(
             self.vault_program.clone(),
-            Combine {
+            CombineVault {
                 vault: self.vault.to_account_info(),
-                vault_authority: self.authority.to_account_info(),
-                vault_pda: self.vault_pda.to_account_info(),
-                external_pricing_account: self.external_pricing_account.to_account_info(),
                 outstanding_share_token_account: self.outstanding_fractions_token_account.to_account_info(),
-                paying_token_account: self.paying_token_account.to_account_info(),
+                paying_token_account: paying_token_account.clone(),
                 fraction_mint: self.fraction_mint.to_account_info(),
                 fraction_treasury: self.vault_fraction_treasury.to_account_info(),
                 redeem_treasury: self.vault_redeem_treasury.to_account_info(),
-                payer: self.bidder.to_account_info(),
+                new_vault_authority: self.authority.to_account_info(),
+                vault_authority: self.authority.to_account_info(),
+                paying_transfer_authority: paying_transfer_authority.clone(),
+                vault_pda: self.vault_pda.to_account_info(),
+                external_pricing_account: self.external_pricing_account.clone(),
                 token_program: self.token_program.to_account_info(),
             }
         )
     }
 
+    fn escrow_fractions(&self, fractions: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.paying_fraction_account.to_account_info(),
+                    to: self.fraction_escrow.to_account_info(),
+                    authority: self.funding_authority.to_account_info()
+                }
+            ),
+            fractions
+        )?;
+        Ok(())
+    }
+
+    fn wrap_into_treasury(&self, amount: u64) -> ProgramResult {
+        native::wrap(
+            &self.funding_authority.to_account_info(),
+            &self.payment_treasury.to_account_info(),
+            &self.system_program.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount
+        )
+    }
+
     fn transfer_to_treasury(&self, amount: u64) -> ProgramResult {
         token::transfer(
             CpiContext::new(
@@ -150,7 +214,7 @@ impl<'info> Start<'info> {
                 Transfer {
                     from: self.paying_token_account.to_account_info(),
                     to: self.payment_treasury.to_account_info(),
-                    authority: self.bidder.to_account_info()
+                    authority: self.funding_authority.to_account_info()
                 }
             ),
             amount
@@ -159,74 +223,74 @@ impl<'info> Start<'info> {
     }
 }
 
-#[derive(Accounts)]
-struct Combine<'info> {
-    pub vault: AccountInfo<'info>,
-    pub vault_authority: AccountInfo<'info>,
-    pub vault_pda: AccountInfo<'info>,
-    pub external_pricing_account: AccountInfo<'info>,
-    pub outstanding_share_token_account: AccountInfo<'info>,
-    pub paying_token_account: AccountInfo<'info>,
-    pub fraction_mint: AccountInfo<'info>,
-    pub fraction_treasury: AccountInfo<'info>,
-    pub redeem_treasury: AccountInfo<'info>,
-    pub payer: AccountInfo<'info>,
-    pub token_program: AccountInfo<'info>
-}
-
-
-fn combine_vault<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, Combine<'info>>) -> ProgramResult {
-    let ix = create_combine_vault_instruction(
-        Vault::owner(),
-        *ctx.accounts.vault.key,
-        *ctx.accounts.outstanding_share_token_account.key,
-        *ctx.accounts.paying_token_account.key,
-        *ctx.accounts.fraction_mint.key,
-        *ctx.accounts.fraction_treasury.key,
-        *ctx.accounts.redeem_treasury.key,
-        *ctx.accounts.vault_authority.key,
-        *ctx.accounts.vault_authority.key,
-        *ctx.accounts.payer.key,
-        *ctx.accounts.vault_pda.key,
-        *ctx.accounts.external_pricing_account.key,
-    );
-    solana_program::program::invoke_signed(
-        &ix,
-        &[
-            ctx.accounts.vault.clone(),
-            ctx.accounts.outstanding_share_token_account.clone(),
-            ctx.accounts.paying_token_account.clone(),
-            ctx.accounts.fraction_mint.clone(),
-            ctx.accounts.fraction_treasury.clone(),
-            ctx.accounts.redeem_treasury.clone(),
-            ctx.accounts.vault_authority.clone(),
-            ctx.accounts.vault_authority.clone(),
-            ctx.accounts.payer.clone(),
-            ctx.accounts.vault_pda.clone(),
-            ctx.accounts.external_pricing_account.clone(),
-            ctx.accounts.token_program.clone(),
-            ctx.program.clone(),
-        ],
-        ctx.signer_seeds
-    )
-}
-
-
-pub fn handler(ctx: Context<Start>, auction_bump: u8, bid_bump: u8, bid_amount: u64) -> ProgramResult {
+pub fn handler(
+    ctx: Context<Start>,
+    auction_bump: u8,
+    bid_bump: u8,
+    escrow_bump: u8,
+    bid_amount: u64,
+    fractions: u64,
+    refund_account: Option<Pubkey>
+) -> ProgramResult {
     let vault = &ctx.accounts.vault;
-    let external_pricing_account = &ctx.accounts.external_pricing_account;
     let authority = &ctx.accounts.authority;
-    let paying_token_account = &ctx.accounts.paying_token_account;
+    let payment_mint = ctx.accounts.payment_mint.key();
+    let funding_authority = ctx.accounts.funding_authority.key();
+    let external_pricing_account = utils::load_external_pricing_account(authority, &ctx.accounts.external_pricing_account)?;
+    let is_native = native::is_native_payment(&payment_mint, &ctx.accounts.paying_token_account, &funding_authority);
+
+    // A listing is only taken over while nobody has bid on it and it has not expired,
+    // with the settings and payment treasury it was listed with
+    let listed = ctx.accounts.auction.vault != Pubkey::default();
+    if listed {
+        let auction = &ctx.accounts.auction;
+        utils::assert_auction_has_not_ended(auction)?;
+        utils::assert_auction_not_combined(auction)?;
+        utils::assert_listing_accounts(auction, &ctx.accounts.settings.key(), &ctx.accounts.payment_treasury.key())?;
+    }
 
     // Check the vault information is correct and ready to combine
-    utils::assert_sufficient_funds(paying_token_account, bid_amount)?;
+    utils::assert_vault_program(&ctx.accounts.vault_program)?;
+    utils::assert_vault_pda(&vault.key(), &ctx.accounts.vault_pda)?;
     utils::assert_vault_owned_by_auction_program_authority(vault, authority)?;
-    utils::assert_correct_pricing_account(vault, external_pricing_account)?;
-    utils::assert_vault_allowed_to_combine(external_pricing_account)?;
+    utils::assert_correct_pricing_account(vault, &ctx.accounts.external_pricing_account)?;
+    utils::assert_payment_mint_matches_price_mint(&external_pricing_account, &ctx.accounts.payment_mint.key())?;
+    utils::assert_vault_allowed_to_combine(&external_pricing_account)?;
     utils::assert_vault_in_active_state(vault)?;
 
-    // Combine the vault!
-    let reserve_price = ctx.accounts.combine_vault()?;
+    // Escrow any fractions the bidder holds, crediting them with those fractions' share of the bid.
+    // Fractions in the outstanding fractions account are burnt by the combination instead,
+    // which credits them against the reserve price.
+    if fractions > 0 {
+        let paying_fraction_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.paying_fraction_account)?;
+        utils::assert_token_account_mint(&paying_fraction_account, &ctx.accounts.fraction_mint.key())?;
+        utils::assert_sufficient_funds(&paying_fraction_account, fractions)?;
+        ctx.accounts.escrow_fractions(fractions)?;
+        ctx.accounts.outstanding_fractions_token_account.reload()?;
+    }
+    let fraction_supply = utils::calculate_circulating_supply(
+        ctx.accounts.fraction_mint.supply,
+        &ctx.accounts.vault_fraction_treasury,
+        ctx.accounts.outstanding_fractions_token_account.amount
+    )?;
+    let fraction_credit = utils::calculate_fraction_credit(bid_amount, fractions, fraction_supply)?;
+    let payment = bid_amount - fraction_credit;
+
+    // Combine the vault! When bidding in native SOL, the whole payment is wrapped into
+    // the payment treasury and the treasury pays for the combination.
+    let reserve_price = if is_native {
+        let payment_treasury = ctx.accounts.payment_treasury.to_account_info();
+        let auction = ctx.accounts.auction.to_account_info();
+        ctx.accounts.wrap_into_treasury(payment)?;
+        ctx.accounts.combine_vault(&payment_treasury, &auction, auction_bump)?
+    } else {
+        let paying_token_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.paying_token_account)?;
+        utils::assert_token_account_mint(&paying_token_account, &payment_mint)?;
+        utils::assert_sufficient_funds(&paying_token_account, payment)?;
+        // The token program makes sure the funding authority is the owner or a delegate
+        let funding_authority = ctx.accounts.funding_authority.to_account_info();
+        ctx.accounts.combine_vault(&ctx.accounts.paying_token_account, &funding_authority, auction_bump)?
+    };
     msg!("reserve-price: {}", reserve_price);
 
     let settings = &ctx.accounts.settings;
@@ -234,34 +298,53 @@ pub fn handler(ctx: Context<Start>, auction_bump: u8, bid_bump: u8, bid_amount:
     msg!("minimum-bid: {}", minimum_bid);
 
     utils::assert_bid_meets_reserve_price(bid_amount, minimum_bid)?;
+    if payment < reserve_price {
+        return Err(AuctionError::FractionCreditTooLarge.into());
+    }
 
-    // Transfer the remaining bid amount to the payment treasury
-    ctx.accounts.transfer_to_treasury(bid_amount - reserve_price)?;
+    // Transfer the remaining payment to the payment treasury
+    if !is_native {
+        ctx.accounts.transfer_to_treasury(payment - reserve_price)?;
+    }
 
     let bid = &mut ctx.accounts.bid;
     let auction = &mut ctx.accounts.auction;
     let current_timestamp = utils::get_current_timestamp()?;
 
     // Set the bid information
-    bid.bidder = ctx.accounts.bidder.key();
+    bid.bidder = ctx.accounts.beneficiary.key();
     bid.auction = auction.key();
     bid.amount = bid_amount;
     bid.timestamp = current_timestamp;
     bid.withdrawable = false;
     bid.token_account = ctx.accounts.bid_token_account.key();
+    bid.refund_account = refund_account;
     bid.bump = bid_bump;
-
-    // Set the auction information
-    auction.start_timestamp = current_timestamp;
-    auction.end_timestamp = current_timestamp + settings.duration;
+    bid.escrowed_fractions = fractions;
+    bid.fraction_credit = fraction_credit;
+    bid.fraction_account = ctx.accounts.paying_fraction_account.key();
+    bid.escrow_bump = escrow_bump;
+
+    // Set the auction information. A listing keeps its deadline, extended if needed.
+    if listed {
+        let time_remaining = auction.end_timestamp - current_timestamp;
+        if time_remaining < settings.soft_close_period {
+            auction.end_timestamp = current_timestamp + settings.soft_close_period;
+        }
+    } else {
+        auction.start_timestamp = current_timestamp;
+        auction.end_timestamp = current_timestamp + settings.duration;
+        auction.lister = ctx.accounts.payer.key();
+    }
     auction.payment_mint = ctx.accounts.payment_mint.key();
     auction.top_bid = bid_amount;
-    auction.top_bidder = ctx.accounts.bidder.key();
+    auction.top_bidder = ctx.accounts.beneficiary.key();
     auction.reserve_price = reserve_price;
     auction.settings = settings.key();
     auction.vault = ctx.accounts.vault.key();
     auction.payment_treasury = ctx.accounts.payment_treasury.key();
     auction.bump = auction_bump;
+    auction.combined = true;
 
     solana_program::log::sol_log_compute_units();
 
@@ -4,8 +4,8 @@ use anchor_lang::solana_program::sysvar::clock::Clock;
 use std::result::Result;
 
 use crate::errors::*;
-use crate::state::{Auction, Authority, MAX_FACILITATOR_FEE, MAX_BID_INCREMENT};
-use crate::vault::{Vault, Price, VaultState};
+use crate::state::{Auction, Authority, Bid, Offer, MAX_FACILITATOR_FEE, MAX_BID_INCREMENT};
+use crate::vault::{Vault, Price, VaultState, SafetyDepositBox, find_vault_pda, price_tracker_program};
 
 pub fn get_current_timestamp() -> Result<u64, ProgramError> {
     Ok(Clock::get()?.unix_timestamp as u64)
@@ -41,6 +41,108 @@ pub fn assert_auction_has_not_ended(auction: &Auction) -> ProgramResult {
     Ok(())
 }
 
+pub fn assert_auction_combined(auction: &Auction) -> ProgramResult {
+    if !auction.combined {
+        return Err(AuctionError::AuctionNotCombined.into());
+    }
+    Ok(())
+}
+
+pub fn assert_auction_not_combined(auction: &Auction) -> ProgramResult {
+    if auction.combined {
+        return Err(AuctionError::AuctionAlreadyCombined.into());
+    }
+    Ok(())
+}
+
+pub fn assert_listing_accounts(auction: &Auction, settings: &Pubkey, payment_treasury: &Pubkey) -> ProgramResult {
+    if auction.settings != *settings || auction.payment_treasury != *payment_treasury {
+        return Err(AuctionError::ListingMismatch.into());
+    }
+    Ok(())
+}
+
+pub fn assert_offer_has_not_expired(offer: &Offer) -> ProgramResult {
+    let current_timestamp = get_current_timestamp()?;
+    if current_timestamp >= offer.expiry {
+        return Err(AuctionError::OfferHasExpired.into());
+    }
+    Ok(())
+}
+
+pub fn assert_offer_has_expired(offer: &Offer) -> ProgramResult {
+    let current_timestamp = get_current_timestamp()?;
+    if current_timestamp < offer.expiry {
+        return Err(AuctionError::OfferHasNotExpired.into());
+    }
+    Ok(())
+}
+
+pub fn assert_offer_not_accepted(offer: &Offer) -> ProgramResult {
+    if offer.accepted {
+        return Err(AuctionError::OfferAlreadyAccepted.into());
+    }
+    Ok(())
+}
+
+pub fn assert_offer_has_majority(offer: &Offer, circulating_supply: u64) -> ProgramResult {
+    if offer.votes <= circulating_supply / 2 {
+        return Err(AuctionError::OfferLacksMajority.into());
+    }
+    Ok(())
+}
+
+pub fn assert_bid_account<'info>(bid: &Account<'info, Bid>, bidder: &Pubkey, auction: &Pubkey) -> ProgramResult {
+    if bid.bidder != *bidder || bid.auction != *auction {
+        return Err(AuctionError::InvalidBidAccount.into());
+    }
+    match Pubkey::create_program_address(
+        &[b"bid".as_ref(), bidder.as_ref(), auction.as_ref(), &[bid.bump]],
+        &crate::id()
+    ) {
+        Ok(expected_bid) if expected_bid == bid.key() => Ok(()),
+        _ => Err(AuctionError::InvalidBidAccount.into())
+    }
+}
+
+pub fn assert_bid_token_account(bid: &Bid, bid_token_account: &Pubkey) -> ProgramResult {
+    if bid.token_account != *bid_token_account {
+        return Err(AuctionError::BidTokenAccountMismatch.into());
+    }
+    Ok(())
+}
+
+pub fn assert_bid_escrow<'info>(bid: &Account<'info, Bid>, fraction_escrow: &Pubkey) -> ProgramResult {
+    match Pubkey::create_program_address(
+        &[b"escrow".as_ref(), bid.key().as_ref(), &[bid.escrow_bump]],
+        &crate::id()
+    ) {
+        Ok(expected_escrow) if expected_escrow == *fraction_escrow => Ok(()),
+        _ => Err(AuctionError::InvalidBidAccount.into())
+    }
+}
+
+pub fn assert_token_account_mint(token_account: &TokenAccount, mint: &Pubkey) -> ProgramResult {
+    if token_account.mint != *mint {
+        return Err(AuctionError::TokenAccountMintMismatch.into());
+    }
+    Ok(())
+}
+
+pub fn assert_token_account_owner(token_account: &TokenAccount, owner: &Pubkey) -> ProgramResult {
+    if token_account.owner != *owner {
+        return Err(AuctionError::TokenAccountOwnerMismatch.into());
+    }
+    Ok(())
+}
+
+pub fn assert_fraction_account(bid: &Bid, fraction_account: &Pubkey) -> ProgramResult {
+    if bid.fraction_account != *fraction_account {
+        return Err(AuctionError::FractionAccountMismatch.into());
+    }
+    Ok(())
+}
+
 pub fn assert_sufficient_funds(token_account: &TokenAccount, amount: u64) -> ProgramResult {
     if token_account.amount < amount {
         return Err(AuctionError::InsufficientFunds.into());
@@ -48,6 +150,42 @@ pub fn assert_sufficient_funds(token_account: &TokenAccount, amount: u64) -> Pro
     Ok(())
 }
 
+pub fn assert_vault_program(vault_program: &AccountInfo) -> ProgramResult {
+    if *vault_program.key != Vault::owner() || !vault_program.executable {
+        return Err(AuctionError::InvalidVaultProgram.into());
+    }
+    Ok(())
+}
+
+pub fn assert_price_program(price_program: &AccountInfo) -> ProgramResult {
+    if *price_program.key != price_tracker_program::ID || !price_program.executable {
+        return Err(AuctionError::InvalidPriceProgram.into());
+    }
+    Ok(())
+}
+
+pub fn assert_price_tracked(external_pricing_account: &AccountInfo) -> ProgramResult {
+    if *external_pricing_account.owner != price_tracker_program::ID {
+        return Err(AuctionError::PriceNotTracked.into());
+    }
+    Ok(())
+}
+
+pub fn assert_auction_program(auction_program: &AccountInfo) -> ProgramResult {
+    if *auction_program.key != crate::id() || !auction_program.executable {
+        return Err(AuctionError::InvalidAuctionProgram.into());
+    }
+    Ok(())
+}
+
+pub fn assert_vault_pda(vault: &Pubkey, vault_pda: &AccountInfo) -> ProgramResult {
+    let (expected_vault_pda, _) = find_vault_pda(vault);
+    if *vault_pda.key != expected_vault_pda {
+        return Err(AuctionError::InvalidVaultPda.into());
+    }
+    Ok(())
+}
+
 pub fn assert_vault_owned_by_auction_program_authority<'info>(vault: &Vault, authority: &Account<'info, Authority>) -> ProgramResult {
     if vault.authority != authority.key() {
         return Err(AuctionError::VaultNotOwnedByAuctionAuthority.into());
@@ -55,8 +193,15 @@ pub fn assert_vault_owned_by_auction_program_authority<'info>(vault: &Vault, aut
     Ok(())
 }
 
-pub fn assert_correct_pricing_account<'info>(vault: &Vault, external_pricing_account: &Account<'info, Price>) -> ProgramResult {
-    if vault.pricing_lookup_address != external_pricing_account.key() {
+pub fn load_external_pricing_account<'info>(authority: &Authority, external_pricing_account: &AccountInfo<'info>) -> Result<Price, ProgramError> {
+    if !authority.trusted_price_programs.contains(external_pricing_account.owner) {
+        return Err(AuctionError::UntrustedPriceProgram.into());
+    }
+    Price::try_deserialize(&mut &external_pricing_account.try_borrow_data()?[..])
+}
+
+pub fn assert_correct_pricing_account<'info>(vault: &Vault, external_pricing_account: &AccountInfo<'info>) -> ProgramResult {
+    if vault.pricing_lookup_address != *external_pricing_account.key {
         return Err(AuctionError::PriceAccountDoesNotMatchVaultAccount.into());
     }
     Ok(())
@@ -69,6 +214,31 @@ pub fn assert_vault_allowed_to_combine(external_pricing_account: &Price) -> Prog
     Ok(())
 }
 
+pub fn assert_vault_not_allowed_to_combine(external_pricing_account: &Price) -> ProgramResult {
+    if external_pricing_account.allowed_to_combine {
+        return Err(AuctionError::VaultAllowedToCombine.into());
+    }
+    Ok(())
+}
+
+pub fn assert_auction_not_started(vault: &Pubkey, auction: &AccountInfo) -> ProgramResult {
+    let (expected_auction, _) = Pubkey::find_program_address(&[b"auction".as_ref(), vault.as_ref()], &crate::id());
+    if *auction.key != expected_auction {
+        return Err(AuctionError::InvalidAuctionAccount.into());
+    }
+    if !auction.data_is_empty() {
+        return Err(AuctionError::AuctionAlreadyStarted.into());
+    }
+    Ok(())
+}
+
+pub fn assert_payment_mint_matches_price_mint(external_pricing_account: &Price, payment_mint: &Pubkey) -> ProgramResult {
+    if external_pricing_account.price_mint != *payment_mint {
+        return Err(AuctionError::PaymentMintDoesNotMatchPriceMint.into());
+    }
+    Ok(())
+}
+
 pub fn assert_vault_in_active_state(vault: &Vault) -> ProgramResult {
     if vault.state != VaultState::Active {
         return Err(AuctionError::VaultNotInActiveState.into());
@@ -98,6 +268,13 @@ pub fn assert_not_top_bidder(bidder: &Pubkey, top_bidder: &Pubkey) -> ProgramRes
     Ok(())
 }
 
+pub fn assert_pool_not_top_bidder(pool: &Pubkey, top_bidder: &Pubkey) -> ProgramResult {
+    if top_bidder == pool {
+        return Err(AuctionError::PoolHoldsTopBid.into());
+    }
+    Ok(())
+}
+
 pub fn assert_auction_winner(claimer: &Pubkey, winner: &Pubkey) -> ProgramResult {
     if winner != claimer {
         return Err(AuctionError::NotAuctionWinner.into());
@@ -105,6 +282,37 @@ pub fn assert_auction_winner(claimer: &Pubkey, winner: &Pubkey) -> ProgramResult
     Ok(())
 }
 
+pub fn assert_safety_deposit_box_in_vault(safety_deposit_box: &SafetyDepositBox, vault: &Pubkey) -> ProgramResult {
+    if safety_deposit_box.vault != *vault {
+        return Err(AuctionError::SafetyDepositBoxNotInVault.into());
+    }
+    Ok(())
+}
+
+pub fn assert_safety_deposit_box_store(safety_deposit_box: &SafetyDepositBox, store: &Pubkey) -> ProgramResult {
+    if safety_deposit_box.store != *store {
+        return Err(AuctionError::SafetyDepositBoxStoreMismatch.into());
+    }
+    Ok(())
+}
+
+pub fn assert_safety_deposit_box_not_claimed(auction: &Auction, safety_deposit_box: &SafetyDepositBox) -> ProgramResult {
+    if auction.is_box_claimed(safety_deposit_box.order) {
+        return Err(AuctionError::SafetyDepositBoxAlreadyClaimed.into());
+    }
+    Ok(())
+}
+
+pub fn assert_claim_destination(destination: &TokenAccount, safety_deposit_box: &SafetyDepositBox, winner: &Pubkey) -> ProgramResult {
+    if destination.mint != safety_deposit_box.token_mint {
+        return Err(AuctionError::DestinationMintMismatch.into());
+    }
+    if destination.owner != *winner {
+        return Err(AuctionError::DestinationNotOwnedByWinner.into());
+    }
+    Ok(())
+}
+
 pub fn calculate_minimum_starting_bid(reserve_price: u64, facilitator_fee: u64) -> Result<u64, ProgramError> {
     Ok(match (reserve_price as u128).checked_mul(MAX_FACILITATOR_FEE as u128) {
         Some(x) => match x.checked_div((MAX_FACILITATOR_FEE - facilitator_fee) as u128) {
@@ -143,4 +351,48 @@ pub fn calculate_redeem_payment(remaining: u64, amount: u64, fraction_supply: u6
         },
         None => return Err(AuctionError::NumericalOverflowError.into()),
     })
+}
+
+pub fn calculate_pool_share(pool_balance: u64, contribution: u64, total_contributions: u64) -> Result<u64, ProgramError> {
+    Ok(match (pool_balance as u128).checked_mul(contribution as u128) {
+        Some(x) => match x.checked_div(total_contributions as u128) {
+            Some(val) => val as u64,
+            None => return Err(AuctionError::NumericalOverflowError.into()),
+        },
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    })
+}
+
+// The fractions which stay in circulation once the vault is combined. Those in the vault's
+// fraction treasury, and any outstanding fractions paid in by the combination, are burned by it.
+pub fn calculate_circulating_supply(fraction_supply: u64, fraction_treasury: &TokenAccount, outstanding_fractions: u64) -> Result<u64, ProgramError> {
+    let supply = match fraction_supply.checked_sub(fraction_treasury.amount) {
+        Some(val) => val,
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    };
+    match supply.checked_sub(outstanding_fractions) {
+        Some(val) => Ok(val),
+        None => Err(AuctionError::NumericalOverflowError.into()),
+    }
+}
+
+pub fn calculate_fraction_credit(bid_amount: u64, fractions: u64, fraction_supply: u64) -> Result<u64, ProgramError> {
+    Ok(match (bid_amount as u128).checked_mul(fractions as u128) {
+        Some(x) => match x.checked_div(fraction_supply as u128) {
+            Some(val) => val as u64,
+            None => return Err(AuctionError::NumericalOverflowError.into()),
+        },
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    })
+}
+
+// The price per fraction in circulation at which combining the vault, and the facilitator fee on top, costs no more than the offer
+pub fn calculate_offer_price_per_fraction(amount: u64, facilitator_fee: u64, circulating_supply: u64) -> Result<u64, ProgramError> {
+    Ok(match (amount as u128).checked_mul((MAX_FACILITATOR_FEE - facilitator_fee) as u128) {
+        Some(x) => match x.checked_div(MAX_FACILITATOR_FEE as u128 * circulating_supply as u128) {
+            Some(val) => val as u64,
+            None => return Err(AuctionError::NumericalOverflowError.into()),
+        },
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    })
 }
\ No newline at end of file
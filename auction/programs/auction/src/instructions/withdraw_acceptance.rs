@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount, CloseAccount};
+
+use crate::errors::*;
+use crate::state::{Offer, OfferVote};
+
+#[derive(Accounts)]
+pub struct WithdrawAcceptance<'info> {
+
+    #[account(
+        mut,
+        seeds = [b"offer".as_ref(), offer.vault.as_ref(), offer.bidder.as_ref()],
+        bump = offer.bump,
+        has_one = bidder,
+        has_one = fraction_account
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    #[account(
+        seeds = [b"offer_authority".as_ref(), offer.key().as_ref()],
+        bump = offer.authority_bump
+    )]
+    pub offer_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offer_vote".as_ref(), offer.key().as_ref(), voter.key().as_ref()],
+        bump = offer_vote.bump,
+        has_one = offer,
+        has_one = voter
+    )]
+    pub offer_vote: Box<Account<'info, OfferVote>>,
+
+    #[account(mut)]
+    pub fraction_account: Box<Account<'info, TokenAccount>>,
+
+    // The token program makes sure it is of the fraction mint.
+    #[account(mut)]
+    pub voter_fraction_account: Box<Account<'info, TokenAccount>>,
+
+    // Paid for the vote record, and gets its rent back once it is emptied.
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    // Gets back the rent of a refunded offer with the last withdrawal.
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+impl<'info> WithdrawAcceptance<'info> {
+
+    fn transfer_to_voter(&self, votes: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.fraction_account.to_account_info(),
+                    to: self.voter_fraction_account.to_account_info(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            ),
+            votes
+        )?;
+        Ok(())
+    }
+
+    fn close_fraction_account(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.fraction_account.to_account_info(),
+                    destination: self.bidder.clone(),
+                    authority: self.offer_authority.clone()
+                },
+                &[&[b"offer_authority".as_ref(), self.offer.key().as_ref(), &[self.offer.authority_bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
+}
+
+pub fn handler(ctx: Context<WithdrawAcceptance>, votes: u64) -> ProgramResult {
+    // Acceptances can be withdrawn at any time, including once the offer has been
+    // accepted, when the fractions are needed to redeem a share of the payment
+    let offer_vote = &mut ctx.accounts.offer_vote;
+    let counted = offer_vote.take_votes(votes)?;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.votes = match offer.votes.checked_sub(counted) {
+        Some(val) => val,
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    };
+
+    ctx.accounts.transfer_to_voter(votes)?;
+
+    let offer_vote = &ctx.accounts.offer_vote;
+    if offer_vote.votes == 0 && offer_vote.pending_votes == 0 {
+        offer_vote.close(ctx.accounts.voter.to_account_info())?;
+    }
+
+    // The last withdrawal from a refunded offer closes it
+    if ctx.accounts.offer.refunded && token::accessor::amount(&ctx.accounts.fraction_account.to_account_info())? == 0 {
+        ctx.accounts.close_fraction_account()?;
+        ctx.accounts.offer.close(ctx.accounts.bidder.clone())?;
+    }
+
+    Ok(())
+}
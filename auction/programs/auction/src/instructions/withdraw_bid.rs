@@ -3,6 +3,7 @@ use anchor_spl::token::{self, Transfer, Token, TokenAccount, CloseAccount};
 
 use crate::state::{Auction, Bid, Settings};
 use crate::vault::Vault;
+use crate::native;
 use crate::instructions::utils;
 
 #[derive(Accounts)]
@@ -21,11 +22,11 @@ pub struct WithdrawBid<'info> {
 
     #[account(
         mut,
-        seeds = [b"bid".as_ref(), bidder.key().as_ref(), auction.key().as_ref()],
+        seeds = [b"bid".as_ref(), beneficiary.key().as_ref(), auction.key().as_ref()],
         bump = bid.bump,
         constraint = bid.token_account == bid_token_account.key(),
-        has_one = bidder,
-        close = bidder
+        constraint = bid.bidder == beneficiary.key(),
+        close = beneficiary
     )]
     pub bid: Box<Account<'info, Bid>>,
 
@@ -34,12 +35,23 @@ pub struct WithdrawBid<'info> {
 
     #[account(
         mut,
-        constraint = to_account.mint == auction.payment_mint
+        seeds = [b"escrow".as_ref(), bid.key().as_ref()],
+        bump = bid.escrow_bump
     )]
-    pub to_account: Box<Account<'info, TokenAccount>>,
+    pub fraction_escrow: Box<Account<'info, TokenAccount>>,
 
+    // Either a token account of the payment mint or, for native SOL auctions, 
+    // the beneficiary's own wallet.
     #[account(mut)]
-    pub bidder: Signer<'info>,
+    pub to_account: AccountInfo<'info>,
+
+    // A token account of the fraction mint, which receives any escrowed fractions.
+    #[account(mut)]
+    pub fraction_to_account: AccountInfo<'info>,
+
+    // The beneficiary of the bid, who is recorded as its bidder.
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
 
     pub token_program: Program<'info, Token>
 
@@ -56,23 +68,54 @@ impl<'info> WithdrawBid<'info> {
                     to: self.to_account.to_account_info(),
                     authority: self.bid.to_account_info()
                 },
-                &[&[b"bid".as_ref(), self.bidder.key().as_ref(), self.auction.key().as_ref(), &[self.bid.bump]]]
+                &[&[b"bid".as_ref(), self.beneficiary.key().as_ref(), self.auction.key().as_ref(), &[self.bid.bump]]]
             ), 
             self.bid_token_account.amount
         )?;
         Ok(())
     }
 
+    fn return_fractions_to_bidder(&self) -> ProgramResult {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.fraction_escrow.to_account_info(),
+                    to: self.fraction_to_account.clone(),
+                    authority: self.bid.to_account_info()
+                },
+                &[&[b"bid".as_ref(), self.beneficiary.key().as_ref(), self.auction.key().as_ref(), &[self.bid.bump]]]
+            ),
+            self.fraction_escrow.amount
+        )?;
+        Ok(())
+    }
+
+    fn close_fraction_escrow(&self) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.fraction_escrow.to_account_info(),
+                    destination: self.beneficiary.to_account_info(),
+                    authority: self.bid.to_account_info()
+                },
+                &[&[b"bid".as_ref(), self.beneficiary.key().as_ref(), self.auction.key().as_ref(), &[self.bid.bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
     fn close_bid_account(&self) -> ProgramResult {
         token::close_account(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 CloseAccount {
                     account: self.bid_token_account.to_account_info(),
-                    destination: self.bidder.to_account_info(),
+                    destination: self.beneficiary.to_account_info(),
                     authority: self.bid.to_account_info()
                 },
-                &[&[b"bid".as_ref(), self.bidder.key().as_ref(), self.auction.key().as_ref(), &[self.bid.bump]]]
+                &[&[b"bid".as_ref(), self.beneficiary.key().as_ref(), self.auction.key().as_ref(), &[self.bid.bump]]]
             )
         )?;
         Ok(())
@@ -88,11 +131,24 @@ pub fn handler(ctx: Context<WithdrawBid>) -> ProgramResult {
     utils::assert_not_top_bidder(&bid.bidder, &auction.top_bidder)?;
 
     // Transfer all the funds from their bidding token account to the token account
-    // they specify, which does not necessarily have to be owned by them.
-    ctx.accounts.return_funds_to_bidder()?;
+    // they specify, which does not necessarily have to be owned by them. Native SOL
+    // is instead unwrapped into the beneficiary's wallet by closing the bidding account.
+    if !native::is_native_payment(&auction.payment_mint, &ctx.accounts.to_account, &bid.bidder) {
+        let to_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.to_account)?;
+        utils::assert_token_account_mint(&to_account, &auction.payment_mint)?;
+        ctx.accounts.return_funds_to_bidder()?;
+    }
+
+    // Return any escrowed fractions, again to a token account of their choosing
+    if ctx.accounts.fraction_escrow.amount > 0 {
+        let fraction_to_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.fraction_to_account)?;
+        utils::assert_token_account_mint(&fraction_to_account, &ctx.accounts.fraction_escrow.mint)?;
+        ctx.accounts.return_fractions_to_bidder()?;
+    }
 
-    // Close their bidding account and return the rent to the bidder.
+    // Close their bidding accounts and return the rent to the beneficiary.
     ctx.accounts.close_bid_account()?;
+    ctx.accounts.close_fraction_escrow()?;
 
     Ok(())
 }
\ No newline at end of file
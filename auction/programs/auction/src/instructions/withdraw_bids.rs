@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount, CloseAccount};
+
+use crate::errors::*;
+use crate::state::{Auction, Bid};
+use crate::native;
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct WithdrawBids<'info> {
+
+    // Either a token account of the payment mint or, for native SOL auctions,
+    // the beneficiary's own wallet.
+    #[account(mut)]
+    pub to_account: AccountInfo<'info>,
+
+    // The beneficiary of the bids, who is recorded as their bidder.
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    // The bids are passed through the remaining accounts as (auction, bid, bid_token_account,
+    // fraction_escrow, fraction_account) groups, one for each bid to withdraw. Any escrowed
+    // fractions are returned to the fraction account registered with the bid.
+
+}
+
+impl<'info> WithdrawBids<'info> {
+
+    fn return_funds_to_bidder(
+        &self,
+        bid: &Account<'info, Bid>,
+        bid_token_account: &Account<'info, TokenAccount>
+    ) -> ProgramResult {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: bid_token_account.to_account_info(),
+                    to: self.to_account.clone(),
+                    authority: bid.to_account_info()
+                },
+                &[&[b"bid".as_ref(), bid.bidder.as_ref(), bid.auction.as_ref(), &[bid.bump]]]
+            ),
+            bid_token_account.amount
+        )?;
+        Ok(())
+    }
+
+    fn return_fractions_to_bidder(
+        &self,
+        bid: &Account<'info, Bid>,
+        fraction_escrow: &Account<'info, TokenAccount>,
+        fraction_account: &AccountInfo<'info>
+    ) -> ProgramResult {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: fraction_escrow.to_account_info(),
+                    to: fraction_account.clone(),
+                    authority: bid.to_account_info()
+                },
+                &[&[b"bid".as_ref(), bid.bidder.as_ref(), bid.auction.as_ref(), &[bid.bump]]]
+            ),
+            fraction_escrow.amount
+        )?;
+        Ok(())
+    }
+
+    fn close_bid_account(
+        &self,
+        bid: &Account<'info, Bid>,
+        bid_token_account: &AccountInfo<'info>
+    ) -> ProgramResult {
+        token::close_account(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: bid_token_account.clone(),
+                    destination: self.beneficiary.to_account_info(),
+                    authority: bid.to_account_info()
+                },
+                &[&[b"bid".as_ref(), bid.bidder.as_ref(), bid.auction.as_ref(), &[bid.bump]]]
+            )
+        )?;
+        Ok(())
+    }
+
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawBids<'info>>) -> ProgramResult {
+    let beneficiary = ctx.accounts.beneficiary.key();
+
+    let chunks = ctx.remaining_accounts.chunks_exact(5);
+    if !chunks.remainder().is_empty() {
+        return Err(AuctionError::InvalidRemainingAccounts.into());
+    }
+
+    for accounts in chunks {
+        let auction: Account<Auction> = Account::try_from(&accounts[0])?;
+        let bid: Account<Bid> = Account::try_from(&accounts[1])?;
+        let bid_token_account: Account<TokenAccount> = Account::try_from(&accounts[2])?;
+        let fraction_escrow: Account<TokenAccount> = Account::try_from(&accounts[3])?;
+
+        // The same checks as for a single withdrawal
+        utils::assert_bid_account(&bid, &beneficiary, &auction.key())?;
+        utils::assert_bid_token_account(&bid, &bid_token_account.key())?;
+        utils::assert_bid_escrow(&bid, &fraction_escrow.key())?;
+        utils::assert_not_top_bidder(&bid.bidder, &auction.top_bidder)?;
+
+        // Native SOL is unwrapped into the beneficiary's wallet by closing the bidding account
+        if !native::is_native_payment(&auction.payment_mint, &ctx.accounts.to_account, &beneficiary) {
+            let to_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.to_account)?;
+            utils::assert_token_account_mint(&to_account, &auction.payment_mint)?;
+            ctx.accounts.return_funds_to_bidder(&bid, &bid_token_account)?;
+        }
+
+        if fraction_escrow.amount > 0 {
+            utils::assert_fraction_account(&bid, accounts[4].key)?;
+            ctx.accounts.return_fractions_to_bidder(&bid, &fraction_escrow, &accounts[4])?;
+        }
+
+        // Close all of the bidding accounts and return the rent to the beneficiary.
+        ctx.accounts.close_bid_account(&bid, &accounts[2])?;
+        ctx.accounts.close_bid_account(&bid, &accounts[3])?;
+        bid.close(ctx.accounts.beneficiary.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+
+use crate::errors::*;
+use crate::state::{Auction, Pool, Contribution};
+use crate::native::{self, Unwrap};
+use crate::instructions::utils;
+
+#[derive(Accounts)]
+pub struct WithdrawContribution<'info> {
+
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), auction.key().as_ref()],
+        bump = pool.bump,
+        has_one = auction,
+        constraint = pool.token_account == pool_token_account.key()
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution".as_ref(), pool.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+        has_one = pool,
+        has_one = contributor,
+        close = contributor
+    )]
+    pub contribution: Box<Account<'info, Contribution>>,
+
+    // Either a token account of the payment mint or, for native SOL auctions,
+    // the contributor's own wallet.
+    #[account(mut)]
+    pub to_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(constraint = auction.payment_mint == payment_mint.key())]
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // Only used to unwrap native SOL withdrawals.
+    #[account(mut)]
+    pub temporary_token_account: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+}
+
+impl<'info> WithdrawContribution<'info> {
+
+    fn transfer_share(&self, amount: u64) -> ProgramResult {
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.pool_token_account.to_account_info(),
+                    to: self.to_account.clone(),
+                    authority: self.pool.to_account_info()
+                },
+                &[&[b"pool".as_ref(), self.auction.key().as_ref(), &[self.pool.bump]]]
+            ),
+            amount
+        )?;
+        Ok(())
+    }
+
+    fn unwrap_share(&self, amount: u64) -> ProgramResult {
+        Unwrap {
+            temporary_token_account: self.temporary_token_account.clone(),
+            native_mint: self.payment_mint.to_account_info(),
+            payer: self.contributor.to_account_info(),
+            destination: self.contributor.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        }.unwrap(
+            &self.pool_token_account.to_account_info(),
+            &self.pool.to_account_info(),
+            &[&[b"pool".as_ref(), self.auction.key().as_ref(), &[self.pool.bump]]],
+            amount
+        )
+    }
+
+}
+
+pub fn handler(ctx: Context<WithdrawContribution>) -> ProgramResult {
+    let auction = &ctx.accounts.auction;
+    let pool = &ctx.accounts.pool;
+    let contribution = &ctx.accounts.contribution;
+
+    // While the pool holds the top bid, and once it has won, the contributions stay put.
+    // Otherwise all of the pool's funds are back in its token account, as place bid
+    // always pushes an outbid pool's refund to the refund account it registered.
+    utils::assert_pool_not_top_bidder(&pool.key(), &auction.top_bidder)?;
+
+    let share = utils::calculate_pool_share(
+        ctx.accounts.pool_token_account.amount,
+        contribution.amount,
+        pool.total_contributions
+    )?;
+
+    if native::is_native_payment(&auction.payment_mint, &ctx.accounts.to_account, &contribution.contributor) {
+        ctx.accounts.unwrap_share(share)?;
+    } else {
+        let to_account: Account<TokenAccount> = Account::try_from(&ctx.accounts.to_account)?;
+        utils::assert_token_account_mint(&to_account, &auction.payment_mint)?;
+        ctx.accounts.transfer_share(share)?;
+    }
+
+    // The contribution account is closed, returning its rent to the contributor
+    let amount = ctx.accounts.contribution.amount;
+    let pool = &mut ctx.accounts.pool;
+    pool.total_contributions = match pool.total_contributions.checked_sub(amount) {
+        Some(val) => val,
+        None => return Err(AuctionError::NumericalOverflowError.into()),
+    };
+
+    Ok(())
+}
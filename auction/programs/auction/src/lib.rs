@@ -4,11 +4,27 @@ pub mod errors;
 pub mod state;
 pub mod instructions;
 pub mod vault;
+pub mod vault_instructions;
+pub mod price_tracker;
+pub mod native;
 
 use instructions::*;
+use state::FractionalizeBumps;
+use price_tracker::VoteSettings;
 
 declare_id!("4h3i7ER3q3pE5LSAedStJaYUcCKd3oXWBKPf3iUWsgGS");
 
+// The cluster features only pick the token vault deployment, see `vault::token_vault_program`.
+#[cfg(not(any(feature = "localnet", feature = "devnet", feature = "mainnet")))]
+compile_error!("One of the `localnet`, `devnet` or `mainnet` features must be enabled");
+
+#[cfg(any(
+    all(feature = "localnet", feature = "devnet"),
+    all(feature = "localnet", feature = "mainnet"),
+    all(feature = "devnet", feature = "mainnet")
+))]
+compile_error!("Only one cluster feature can be enabled, build with `--no-default-features` to pick another cluster than `localnet`");
+
 #[program]
 pub mod auction {
     use super::*;
@@ -21,14 +37,47 @@ pub mod auction {
         instructions::set_owner::handler(ctx, new_owner)
     }
 
+    // Trust the external price accounts of another price program,
+    // e.g. a different price oracle deployment.
+    pub fn add_price_program(ctx: Context<SetAuthority>, price_program: Pubkey) -> ProgramResult {
+        instructions::add_price_program::handler(ctx, price_program)
+    }
+
+    pub fn remove_price_program(ctx: Context<SetAuthority>, price_program: Pubkey) -> ProgramResult {
+        instructions::remove_price_program::handler(ctx, price_program)
+    }
+
     pub fn create_settings(
         ctx: Context<CreateSettings>,
         duration: u64,
         soft_close_period: u64,
         bid_increment: u64,
-        facilitator_fee: u64
+        facilitator_fee: u64,
+        offer_warm_up_period: u64
     ) -> ProgramResult {
-        instructions::create_settings::handler(ctx, duration, soft_close_period, bid_increment, facilitator_fee)
+        instructions::create_settings::handler(ctx, duration, soft_close_period, bid_increment, facilitator_fee, offer_warm_up_period)
+    }
+
+    // Lock an NFT inside a brand new token vault, split it into fractions with the given decimals
+    // for the curator and create the price account which decides when it may be auctioned, with its
+    // quorum and price band in basis points, priced at either the mean or the median vote. Votes only
+    // count after the warm-up period and are locked for the cooldown period, both in seconds.
+    // The program authority is the vault authority from the outset.
+    pub fn fractionalize(
+        ctx: Context<Fractionalize>,
+        bumps: FractionalizeBumps,
+        number_of_fractions: u64,
+        _decimals: u8,
+        min_price: u64,
+        settings: VoteSettings
+    ) -> ProgramResult {
+        instructions::fractionalize::handler(ctx, bumps, number_of_fractions, min_price, settings)
+    }
+
+    // Hand a fractionalized vault back to its curator, which is only possible
+    // while no auction has been started and the vault cannot yet be combined.
+    pub fn release_vault(ctx: Context<ReleaseVault>) -> ProgramResult {
+        instructions::release_vault::handler(ctx)
     }
 
     // Start a new auction for a token vault which contains some asset such as an NFT.
@@ -38,15 +87,38 @@ pub mod auction {
         ctx: Context<Start>,
         auction_bump: u8,
         bid_bump: u8,
-        bid_amount: u64
+        escrow_bump: u8,
+        bid_amount: u64,
+        fractions: u64,
+        refund_account: Option<Pubkey>
     ) -> ProgramResult {
-        instructions::start::handler(ctx, auction_bump, bid_bump, bid_amount)
+        instructions::start::handler(ctx, auction_bump, bid_bump, escrow_bump, bid_amount, fractions, refund_account)
+    }
+
+    // Open a timed auction for a vault which is allowed to combine, without bidding on it.
+    // The first bid on the listing goes through `start`, which combines the vault.
+    pub fn list(ctx: Context<List>, auction_bump: u8) -> ProgramResult {
+        instructions::list::handler(ctx, auction_bump)
+    }
+
+    // Close a listing which expired without any bids, leaving the vault active.
+    pub fn close_listing(ctx: Context<CloseListing>) -> ProgramResult {
+        instructions::close_listing::handler(ctx)
     }
 
     // Place a bid for the contents of the token vault. 
     // Of course, the bid must be higher than the previous by a set amount.
-    pub fn place_bid(ctx: Context<PlaceBid>, bid_bump: u8, bid_amount: u64) -> ProgramResult {
-        instructions::place_bid::handler(ctx, bid_bump, bid_amount)
+    // The bidder may register an account to be refunded to as soon as they are outbid,
+    // and may escrow fractions of the vault to pay part of the bid with.
+    pub fn place_bid(
+        ctx: Context<PlaceBid>,
+        bid_bump: u8,
+        escrow_bump: u8,
+        bid_amount: u64,
+        fractions: u64,
+        refund_account: Option<Pubkey>
+    ) -> ProgramResult {
+        instructions::place_bid::handler(ctx, bid_bump, escrow_bump, bid_amount, fractions, refund_account)
     }
 
     // Withdraw your bid if you have now been outbid.
@@ -54,6 +126,12 @@ pub mod auction {
         instructions::withdraw_bid::handler(ctx)
     }
 
+    // Withdraw any number of outbid bids, across any number of auctions, in one go.
+    // The bids are passed through the remaining accounts.
+    pub fn withdraw_bids<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawBids<'info>>) -> ProgramResult {
+        instructions::withdraw_bids::handler(ctx)
+    }
+
     // Officially end the auction, paying the owner the facilitator fee
     // and allowing redemptions to begin.
     pub fn end(ctx: Context<End>) -> ProgramResult {
@@ -66,11 +144,82 @@ pub mod auction {
         instructions::claim::handler(ctx, amount)
     }
 
+    // Claim the entire contents of every safety deposit box in the auctioned vault
+    // in one go. The boxes are passed through the remaining accounts.
+    pub fn claim_all<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAll<'info>>) -> ProgramResult {
+        instructions::claim_all::handler(ctx)
+    }
+
     // An endpoint where fractions holders can send their fractions in exchange 
     // for their share of the executed auction price.
     pub fn redeem(ctx: Context<Redeem>) -> ProgramResult {
         instructions::redeem::handler(ctx)
     }
 
+    // Create a pool through which many contributors can share a single bid on an auction.
+    pub fn create_pool(ctx: Context<CreatePool>, pool_bump: u8) -> ProgramResult {
+        instructions::create_pool::handler(ctx, pool_bump)
+    }
+
+    // Create the account recording a contributor's share of a pool.
+    pub fn join_pool(ctx: Context<JoinPool>, contribution_bump: u8) -> ProgramResult {
+        instructions::join_pool::handler(ctx, contribution_bump)
+    }
+
+    pub fn contribute(ctx: Context<Contribute>, amount: u64) -> ProgramResult {
+        instructions::contribute::handler(ctx, amount)
+    }
+
+    // Place a bid on behalf of the pool, paid from its contributions. The pool is the bidder
+    // and is refunded straight away when outbid, so it can bid again.
+    pub fn pool_bid(ctx: Context<PoolBid>, bid_bump: u8, escrow_bump: u8, bid_amount: u64) -> ProgramResult {
+        instructions::pool_bid::handler(ctx, bid_bump, escrow_bump, bid_amount)
+    }
+
+    // Withdraw a pro-rata share of the pool's funds, as long as the pool does not hold the top bid.
+    pub fn withdraw_contribution(ctx: Context<WithdrawContribution>) -> ProgramResult {
+        instructions::withdraw_contribution::handler(ctx)
+    }
+
+    // Claim the contents of the auctioned token vault into accounts owned by the winning pool.
+    pub fn pool_claim(ctx: Context<PoolClaim>, amount: u64) -> ProgramResult {
+        instructions::pool_claim::handler(ctx, amount)
+    }
+
+    // Post a standing offer to buy a vault outright, escrowing the amount until the expiry.
+    pub fn make_offer(
+        ctx: Context<MakeOffer>,
+        offer_bump: u8,
+        authority_bump: u8,
+        amount: u64,
+        expiry: u64
+    ) -> ProgramResult {
+        instructions::make_offer::handler(ctx, offer_bump, authority_bump, amount, expiry)
+    }
+
+    // Accept an offer by escrowing fractions with it. They only count towards the majority once
+    // the settings' warm-up period has passed and the voter accepts again, with no votes if need be.
+    pub fn accept_offer(ctx: Context<AcceptOffer>, vote_bump: u8, votes: u64) -> ProgramResult {
+        instructions::accept_offer::handler(ctx, vote_bump, votes)
+    }
+
+    // Take back fractions escrowed in acceptance of an offer, whether or not it has been accepted.
+    // The voter's record is closed once it holds no more fractions.
+    pub fn withdraw_acceptance(ctx: Context<WithdrawAcceptance>, votes: u64) -> ProgramResult {
+        instructions::withdraw_acceptance::handler(ctx, votes)
+    }
+
+    // Combine the vault for an offer accepted by a majority of the fractions in circulation, priced
+    // through the price tracker at the offer. The offer is recorded as the winning bid of an auction
+    // which has already ended, so it is settled through `end`, `claim` and `redeem` like any other auction.
+    pub fn execute_offer(ctx: Context<ExecuteOffer>, auction_bump: u8, bid_bump: u8, escrow_bump: u8) -> ProgramResult {
+        instructions::execute_offer::handler(ctx, auction_bump, bid_bump, escrow_bump)
+    }
+
+    // Refund an offer which expired without being accepted, closing it.
+    pub fn refund_offer(ctx: Context<RefundOffer>) -> ProgramResult {
+        instructions::refund_offer::handler(ctx)
+    }
+
 }
 
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{self, system_instruction, program_pack::Pack};
+use anchor_spl::token::{self, Transfer, InitializeAccount, CloseAccount};
+use spl_token::{instruction::sync_native, native_mint};
+
+use crate::errors::AuctionError;
+
+// Helpers allowing auctions denominated in the native mint to take and pay out
+// plain SOL. Lamports are wrapped by sending them straight to a native token
+// account and syncing it. Wrapped lamports can only leave a token account by
+// closing it, so they are unwrapped through a temporary token account.
+
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == native_mint::ID
+}
+
+pub fn is_native_payment(payment_mint: &Pubkey, account: &AccountInfo, wallet: &Pubkey) -> bool {
+    is_native_mint(payment_mint) && account.key == wallet
+}
+
+pub fn find_temporary_token_account(payer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"unwrap".as_ref(), payer.as_ref()], &crate::id())
+}
+
+pub fn wrap<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &system_instruction::transfer(from.key, to.key, amount),
+        &[from.clone(), to.clone(), system_program.clone()]
+    )?;
+    solana_program::program::invoke(
+        &sync_native(token_program.key, to.key)?,
+        &[to.clone(), token_program.clone()]
+    )
+}
+
+#[derive(Accounts)]
+pub struct Unwrap<'info> {
+    pub temporary_token_account: AccountInfo<'info>,
+    pub native_mint: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>
+}
+
+impl<'info> Unwrap<'info> {
+
+    // Create the temporary token account, funded by and under the authority of the payer.
+    pub fn open(&self) -> ProgramResult {
+        let (temporary_token_account, bump) = find_temporary_token_account(self.payer.key);
+        if *self.temporary_token_account.key != temporary_token_account {
+            return Err(AuctionError::InvalidTemporaryTokenAccount.into());
+        }
+
+        let rent = Rent::from_account_info(&self.rent)?;
+        solana_program::program::invoke_signed(
+            &system_instruction::create_account(
+                self.payer.key,
+                self.temporary_token_account.key,
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                self.token_program.key
+            ),
+            &[
+                self.payer.clone(),
+                self.temporary_token_account.clone(),
+                self.system_program.clone(),
+            ],
+            &[&[b"unwrap".as_ref(), self.payer.key.as_ref(), &[bump]]]
+        )?;
+
+        token::initialize_account(
+            CpiContext::new(
+                self.token_program.clone(),
+                InitializeAccount {
+                    account: self.temporary_token_account.clone(),
+                    mint: self.native_mint.clone(),
+                    authority: self.payer.clone(),
+                    rent: self.rent.clone(),
+                }
+            )
+        )
+    }
+
+    // Close the temporary token account, refunding its rent to the payer
+    // and forwarding whatever it held on to the destination.
+    pub fn close(&self) -> ProgramResult {
+        let amount = token::accessor::amount(&self.temporary_token_account)?;
+        token::close_account(
+            CpiContext::new(
+                self.token_program.clone(),
+                CloseAccount {
+                    account: self.temporary_token_account.clone(),
+                    destination: self.payer.clone(),
+                    authority: self.payer.clone(),
+                }
+            )
+        )?;
+
+        if self.destination.key != self.payer.key {
+            solana_program::program::invoke(
+                &system_instruction::transfer(self.payer.key, self.destination.key, amount),
+                &[self.payer.clone(), self.destination.clone(), self.system_program.clone()]
+            )?;
+        }
+        Ok(())
+    }
+
+    // Pay out an amount of wrapped SOL held by one of the program's token accounts.
+    pub fn unwrap(
+        &self,
+        source: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64
+    ) -> ProgramResult {
+        self.open()?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                Transfer {
+                    from: source.clone(),
+                    to: self.temporary_token_account.clone(),
+                    authority: authority.clone()
+                },
+                signer_seeds
+            ),
+            amount
+        )?;
+        self.close()
+    }
+
+}
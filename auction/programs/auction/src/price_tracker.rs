@@ -0,0 +1,201 @@
+use borsh::BorshSerialize;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    self,
+    instruction::{AccountMeta, Instruction},
+    system_program,
+    sysvar
+};
+
+use crate::vault::price_tracker_program;
+
+// Anchor instruction discriminator, the first 8 bytes of sha256("global:initialize").
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+// Mirrors the price tracker's PriceMode, whether a vault is priced at the mean or the median vote.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum PriceMode {
+    Mean,
+    Median,
+}
+
+// Mirrors the price tracker's VoteSettings, how a price account takes votes besides its minimum price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VoteSettings {
+    pub quorum: u64,
+    pub min_band: u64,
+    pub max_band: u64,
+    pub price_mode: PriceMode,
+    pub warm_up_period: u64,
+    pub cooldown_period: u64,
+}
+
+#[derive(BorshSerialize)]
+struct InitializeArgs {
+    pub pda_bump: u8,
+    pub min_price: u64,
+    pub settings: VoteSettings,
+}
+
+// The token accounts which the price tracker leaves out of its quorum are passed last.
+fn excluded_account_metas(excluded_accounts: &[Pubkey]) -> Vec<AccountMeta> {
+    excluded_accounts.iter().map(|key| AccountMeta::new_readonly(*key, false)).collect()
+}
+
+// The price tracker holds the staked fractions of a price account in a store
+// owned by a PDA derived from [b"price", price_account, program_id].
+pub fn find_price_pda(price_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"price".as_ref(), price_account.as_ref(), price_tracker_program::ID.as_ref()],
+        &price_tracker_program::ID
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_initialize_instruction(
+    program_id: Pubkey,
+    price_account: Pubkey,
+    store: Pubkey,
+    store_authority_pda: Pubkey,
+    store_mint: Pubkey,
+    redeem_mint: Pubkey,
+    locked_mint: Pubkey,
+    vault: Pubkey,
+    signer: Pubkey,
+    pda_bump: u8,
+    min_price: u64,
+    settings: VoteSettings,
+    excluded_accounts: &[Pubkey],
+) -> Instruction {
+    let mut data = INITIALIZE_DISCRIMINATOR.to_vec();
+    let args = InitializeArgs { pda_bump, min_price, settings };
+    data.append(&mut args.try_to_vec().unwrap());
+    let mut accounts = vec![
+        AccountMeta::new(price_account, true),
+        AccountMeta::new(store, true),
+        AccountMeta::new(store_authority_pda, false),
+        AccountMeta::new_readonly(store_mint, false),
+        AccountMeta::new_readonly(redeem_mint, false),
+        AccountMeta::new_readonly(locked_mint, false),
+        AccountMeta::new_readonly(vault, false),
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(excluded_account_metas(excluded_accounts));
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePriceAccount<'info> {
+    pub price_account: AccountInfo<'info>,
+    pub store: AccountInfo<'info>,
+    pub store_authority_pda: AccountInfo<'info>,
+    pub store_mint: AccountInfo<'info>,
+    pub redeem_mint: AccountInfo<'info>,
+    pub locked_mint: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub signer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>
+}
+
+pub fn initialize_price_account<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, InitializePriceAccount<'info>>,
+    pda_bump: u8,
+    min_price: u64,
+    settings: VoteSettings
+) -> ProgramResult {
+    let excluded_accounts: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|account| *account.key).collect();
+    let ix = create_initialize_instruction(
+        price_tracker_program::ID,
+        *ctx.accounts.price_account.key,
+        *ctx.accounts.store.key,
+        *ctx.accounts.store_authority_pda.key,
+        *ctx.accounts.store_mint.key,
+        *ctx.accounts.redeem_mint.key,
+        *ctx.accounts.locked_mint.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.signer.key,
+        pda_bump,
+        min_price,
+        settings,
+        &excluded_accounts,
+    );
+    let mut account_infos = vec![
+        ctx.accounts.price_account.clone(),
+        ctx.accounts.store.clone(),
+        ctx.accounts.store_authority_pda.clone(),
+        ctx.accounts.store_mint.clone(),
+        ctx.accounts.redeem_mint.clone(),
+        ctx.accounts.locked_mint.clone(),
+        ctx.accounts.vault.clone(),
+        ctx.accounts.signer.clone(),
+        ctx.accounts.token_program.clone(),
+        ctx.accounts.rent.clone(),
+        ctx.accounts.system_program.clone(),
+        ctx.program.clone(),
+    ];
+    account_infos.extend_from_slice(ctx.remaining_accounts.as_slice());
+    solana_program::program::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds
+    )
+}
+
+// Anchor instruction discriminator, the first 8 bytes of sha256("global:settle_offer").
+const SETTLE_OFFER_DISCRIMINATOR: [u8; 8] = [40, 52, 19, 31, 0, 165, 52, 30];
+
+#[derive(BorshSerialize)]
+struct SettleOfferArgs {
+    pub price_per_share: u64,
+}
+
+pub fn create_settle_offer_instruction(
+    program_id: Pubkey,
+    price_account: Pubkey,
+    auction_authority: Pubkey,
+    price_per_share: u64,
+) -> Instruction {
+    let mut data = SETTLE_OFFER_DISCRIMINATOR.to_vec();
+    data.append(&mut SettleOfferArgs { price_per_share }.try_to_vec().unwrap());
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(price_account, false),
+            AccountMeta::new_readonly(auction_authority, true),
+        ],
+        data,
+    }
+}
+
+#[derive(Accounts)]
+pub struct SettleOffer<'info> {
+    pub price_account: AccountInfo<'info>,
+    pub auction_authority: AccountInfo<'info>
+}
+
+pub fn settle_offer<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, SettleOffer<'info>>, price_per_share: u64) -> ProgramResult {
+    let ix = create_settle_offer_instruction(
+        price_tracker_program::ID,
+        *ctx.accounts.price_account.key,
+        *ctx.accounts.auction_authority.key,
+        price_per_share,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.price_account.clone(),
+            ctx.accounts.auction_authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
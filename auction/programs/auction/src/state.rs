@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
+use std::result::Result;
+
+use crate::errors::*;
 
 pub const MAX_FACILITATOR_FEE: u64 = 1_000_000_000;
 pub const MAX_BID_INCREMENT: u64 = 1_000_000_000;
+pub const MAX_TRUSTED_PRICE_PROGRAMS: usize = 8;
 
 #[account]
 pub struct Auction {
@@ -30,6 +34,16 @@ pub struct Auction {
 
     pub bump: u8,
 
+    // Bitmap of the safety deposit boxes (by their order in the vault)
+    // which have already been emptied by the winner.
+    pub claimed_boxes: [u8; 32],
+
+    // Who opened the auction, and whether the vault has been combined yet. A listing
+    // is opened without a bid and the vault is only combined by its first bid.
+    pub lister: Pubkey,
+
+    pub combined: bool,
+
 }
 
 #[account]
@@ -47,8 +61,23 @@ pub struct Bid {
 
     pub token_account: Pubkey,
 
+    // Where the bid is refunded to as soon as it is outbid, rather than being
+    // held in the bid token account until it is withdrawn.
+    pub refund_account: Option<Pubkey>,
+
     pub bump: u8,
 
+    // Fractions of the vault escrowed with the bid, which the bidder is credited
+    // their pro-rata share of the bid for rather than paying it in full.
+    pub escrowed_fractions: u64,
+
+    pub fraction_credit: u64,
+
+    // Where the escrowed fractions are returned to once the bid is outbid.
+    pub fraction_account: Pubkey,
+
+    pub escrow_bump: u8,
+
 }
 
 #[account]
@@ -56,7 +85,10 @@ pub struct Authority {
 
     pub owner: Pubkey,
 
-    pub bump: u8
+    pub bump: u8,
+
+    // The programs whose external price accounts may be used to start an auction.
+    pub trusted_price_programs: Vec<Pubkey>,
 
 }
 
@@ -71,28 +103,217 @@ pub struct Settings {
 
     pub facilitator_fee: u64,
 
+    // How long, in seconds, an acceptance of an offer takes before it counts.
+    pub offer_warm_up_period: u64,
+
+}
+
+// Records who fractionalized a vault through this program, so that they
+// can take the vault back while it has not been auctioned.
+#[account]
+pub struct Curation {
+
+    pub curator: Pubkey,
+
+    pub vault: Pubkey,
+
+    pub external_pricing_account: Pubkey,
+
+    pub bump: u8,
+
+}
+
+// Pools contributions towards a single bid on an auction, which the pool
+// places itself and which wins the auctioned assets on behalf of everyone.
+#[account]
+pub struct Pool {
+
+    pub auction: Pubkey,
+
+    // Holds the contributions which are not currently tied up in a bid.
+    pub token_account: Pubkey,
+
+    pub total_contributions: u64,
+
+    pub bump: u8,
+
+}
+
+#[account]
+pub struct Contribution {
+
+    pub pool: Pubkey,
+
+    pub contributor: Pubkey,
+
+    pub amount: u64,
+
+    pub bump: u8,
+
+}
+
+// A standing offer to buy a vault outright, escrowed until it expires. Fraction holders
+// accept it by escrowing their fractions with the offer, in token accounts held by the
+// offer authority [b"offer_authority", offer].
+#[account]
+pub struct Offer {
+
+    pub vault: Pubkey,
+
+    pub bidder: Pubkey,
+
+    pub settings: Pubkey,
+
+    pub payment_mint: Pubkey,
+
+    pub escrow: Pubkey,
+
+    // Holds the fractions escrowed in acceptance of the offer.
+    pub fraction_account: Pubkey,
+
+    pub amount: u64,
+
+    pub expiry: u64,
+
+    // The escrowed fractions which have warmed up and count towards the majority.
+    pub votes: u64,
+
+    pub accepted: bool,
+
+    // Refunded while holders still had fractions escrowed, the offer is closed with their last withdrawal.
+    pub refunded: bool,
+
+    pub bump: u8,
+
+    pub authority_bump: u8,
+
+}
+
+#[account]
+pub struct OfferVote {
+
+    pub offer: Pubkey,
+
+    pub voter: Pubkey,
+
+    pub votes: u64,
+
+    // Fractions escrowed since the voter's last acceptance, which only count once warmed up.
+    pub pending_votes: u64,
+
+    pub last_vote_timestamp: u64,
+
+    pub bump: u8,
+
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FractionalizeBumps {
+
+    pub curation: u8,
+
+    pub fraction_mint: u8,
+
+    pub fraction_treasury: u8,
+
+    pub redeem_treasury: u8,
+
+    pub store: u8,
+
+    pub curator_fractions: u8,
+
+    pub price_pda: u8,
+
 }
 
 impl Auction {
     pub fn space() -> usize {
-        return 5 * 8 + 5 * 32 + 2 * 1;
+        return 5 * 8 + 6 * 32 + 3 * 1 + 32;
+    }
+
+    pub fn is_box_claimed(&self, order: u8) -> bool {
+        self.claimed_boxes[(order / 8) as usize] & (1 << (order % 8)) != 0
+    }
+
+    pub fn mark_box_claimed(&mut self, order: u8) {
+        self.claimed_boxes[(order / 8) as usize] |= 1 << (order % 8);
     }
 }
 
 impl Bid {
     pub fn space() -> usize {
-        return 2 * 8 + 3 * 32 + 2;
+        return 4 * 8 + 4 * 32 + 3 + 1 + 32;
     }
 }
 
 impl Authority {
     pub fn space() -> usize {
-        return 1 * 32 + 1;
+        return 1 * 32 + 1 + 4 + MAX_TRUSTED_PRICE_PROGRAMS * 32;
     }
 }
 
 impl Settings {
     pub fn space() -> usize {
-        return 4 * 8;
+        return 5 * 8;
+    }
+}
+
+impl Curation {
+    pub fn space() -> usize {
+        3 * 32 + 1
+    }
+}
+
+impl Pool {
+    pub fn space() -> usize {
+        2 * 32 + 8 + 1
+    }
+}
+
+impl Contribution {
+    pub fn space() -> usize {
+        2 * 32 + 8 + 1
+    }
+}
+
+impl Offer {
+    pub fn space() -> usize {
+        6 * 32 + 3 * 8 + 4
+    }
+}
+
+impl OfferVote {
+    pub fn space() -> usize {
+        2 * 32 + 3 * 8 + 1
+    }
+
+    // Counts the pending votes once warmed up, returning how many were added to the counted votes
+    pub fn activate_votes(&mut self, warm_up_period: u64, now: u64) -> Result<u64, ProgramError> {
+        let active_from = match self.last_vote_timestamp.checked_add(warm_up_period) {
+            Some(val) => val,
+            None => return Err(AuctionError::NumericalOverflowError.into()),
+        };
+        if now < active_from {
+            return Ok(0);
+        }
+        let activated = self.pending_votes;
+        self.votes = match self.votes.checked_add(activated) {
+            Some(val) => val,
+            None => return Err(AuctionError::NumericalOverflowError.into()),
+        };
+        self.pending_votes = 0;
+        Ok(activated)
+    }
+
+    // Takes votes out of the pending votes first, returning how many of those taken had been counted
+    pub fn take_votes(&mut self, votes: u64) -> Result<u64, ProgramError> {
+        let pending = std::cmp::min(votes, self.pending_votes);
+        self.pending_votes -= pending;
+        let counted = votes - pending;
+        self.votes = match self.votes.checked_sub(counted) {
+            Some(val) => val,
+            None => return Err(AuctionError::InsufficientVotes.into()),
+        };
+        Ok(counted)
     }
 }
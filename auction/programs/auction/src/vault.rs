@@ -1,5 +1,4 @@
 
-use std::str::FromStr;
 use std::io::Write;
 use borsh::{BorshDeserialize, BorshSerialize};
 use anchor_lang::solana_program::{
@@ -7,9 +6,12 @@ use anchor_lang::solana_program::{
     pubkey::Pubkey, 
     borsh::try_from_slice_unchecked,
     instruction::{AccountMeta, Instruction},
+    system_program,
     sysvar
 };
 
+use crate::errors::AuctionError;
+
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
 pub enum Key {
@@ -23,7 +25,31 @@ pub const MAX_SAFETY_DEPOSIT_SIZE: usize = 1 + 32 + 32 + 32 + 1;
 pub const MAX_VAULT_SIZE: usize = 1 + 32 + 32 + 32 + 32 + 1 + 32 + 1 + 32 + 1 + 1 + 8;
 pub const MAX_EXTERNAL_ACCOUNT_SIZE: usize = 8 + 1 + 8 + 32 + 1;
 
-//pub const VAULT_PROGRAM_ID: Pubkey = Pubkey::from_str("FMcRJeEKBjD1LU5UyBHvorpep4hVg48ff2C36NX83vtY").unwrap();
+pub const PREFIX: &str = "vault";
+
+// The token vault address depends on the cluster the auction is built for,
+// which is chosen with the `localnet`, `devnet` or `mainnet` feature.
+pub mod token_vault_program {
+    use anchor_lang::prelude::*;
+
+    #[cfg(feature = "localnet")]
+    declare_id!("FMcRJeEKBjD1LU5UyBHvorpep4hVg48ff2C36NX83vtY");
+
+    #[cfg(feature = "devnet")]
+    declare_id!("vau1zxA2LbssAUEF7Gpw91zMM1LvXrvpzJtmZ58rPsn");
+
+    #[cfg(feature = "mainnet")]
+    declare_id!("vau1zxA2LbssAUEF7Gpw91zMM1LvXrvpzJtmZ58rPsn");
+}
+
+// The price tracker is only the price program trusted when the auction house is
+// initialised, the owner can trust further programs through the authority's allowlist.
+// It has no deployments of its own on other clusters yet, so its address is the same on each.
+pub mod price_tracker_program {
+    use anchor_lang::prelude::*;
+
+    declare_id!("D8K3mcxvwDE7ykDPhL6xGXfkWXhbB1YS2nH3MCS1WmsD");
+}
 
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
@@ -52,7 +78,14 @@ pub struct Vault {
 
 impl anchor_lang::AccountDeserialize for Vault {
     fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
-        Vault::try_deserialize_unchecked(buf)
+        if buf.len() != MAX_VAULT_SIZE {
+            return Err(AuctionError::InvalidVaultAccount.into());
+        }
+        let vault = Vault::try_deserialize_unchecked(buf)?;
+        if vault.key != Key::VaultV1 {
+            return Err(AuctionError::InvalidVaultAccount.into());
+        }
+        Ok(vault)
     }
 
     fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
@@ -63,13 +96,53 @@ impl anchor_lang::AccountDeserialize for Vault {
 
 impl anchor_lang::AccountSerialize for Vault {
     fn try_serialize<W: Write>(&self, _writer: &mut W) -> Result<(), ProgramError> {
-        Ok(())
+        Err(AuctionError::ExternalAccountIsReadOnly.into())
     }
 }
 
 impl anchor_lang::Owner for Vault {
     fn owner() -> Pubkey {
-        Pubkey::from_str("FMcRJeEKBjD1LU5UyBHvorpep4hVg48ff2C36NX83vtY").unwrap()
+        token_vault_program::ID
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SafetyDepositBox {
+    pub key: Key,
+    pub vault: Pubkey,
+    pub token_mint: Pubkey,
+    pub store: Pubkey,
+    pub order: u8,
+}
+
+impl anchor_lang::AccountDeserialize for SafetyDepositBox {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        if buf.len() != MAX_SAFETY_DEPOSIT_SIZE {
+            return Err(AuctionError::InvalidSafetyDepositBox.into());
+        }
+        let safety_deposit_box = SafetyDepositBox::try_deserialize_unchecked(buf)?;
+        if safety_deposit_box.key != Key::SafetyDepositBoxV1 {
+            return Err(AuctionError::InvalidSafetyDepositBox.into());
+        }
+        Ok(safety_deposit_box)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        let safety_deposit_box: SafetyDepositBox = try_from_slice_unchecked(buf)?;
+        Ok(safety_deposit_box)
+    }
+}
+
+impl anchor_lang::AccountSerialize for SafetyDepositBox {
+    fn try_serialize<W: Write>(&self, _writer: &mut W) -> Result<(), ProgramError> {
+        Err(AuctionError::ExternalAccountIsReadOnly.into())
+    }
+}
+
+impl anchor_lang::Owner for SafetyDepositBox {
+    fn owner() -> Pubkey {
+        token_vault_program::ID
     }
 }
 
@@ -85,7 +158,14 @@ pub struct Price {
 
 impl anchor_lang::AccountDeserialize for Price {
     fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
-        Price::try_deserialize_unchecked(buf)
+        if buf.len() != MAX_EXTERNAL_ACCOUNT_SIZE {
+            return Err(AuctionError::InvalidPriceAccount.into());
+        }
+        let price = Price::try_deserialize_unchecked(buf)?;
+        if price.key != Key::ExternalAccountKeyV1 {
+            return Err(AuctionError::InvalidPriceAccount.into());
+        }
+        Ok(price)
     }
 
     fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
@@ -96,14 +176,17 @@ impl anchor_lang::AccountDeserialize for Price {
 
 impl anchor_lang::AccountSerialize for Price {
     fn try_serialize<W: Write>(&self, _writer: &mut W) -> Result<(), ProgramError> {
-        Ok(())
+        Err(AuctionError::ExternalAccountIsReadOnly.into())
     }
 }
 
-impl anchor_lang::Owner for Price {
-    fn owner() -> Pubkey {
-        Pubkey::from_str("D8K3mcxvwDE7ykDPhL6xGXfkWXhbB1YS2nH3MCS1WmsD").unwrap()
-    }
+// The token vault program signs for the fraction mint and all the vault's
+// token accounts with a PDA derived from [PREFIX, program_id, vault].
+pub fn find_vault_pda(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), token_vault_program::ID.as_ref(), vault.as_ref()],
+        &token_vault_program::ID
+    )
 }
 
 pub fn create_combine_vault_instruction(
@@ -197,6 +280,201 @@ pub fn create_redeem_shares_instruction(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn create_init_vault_instruction(
+    program_id: Pubkey,
+    fraction_mint: Pubkey,
+    redeem_treasury: Pubkey,
+    fraction_treasury: Pubkey,
+    vault: Pubkey,
+    vault_authority: Pubkey,
+    external_pricing_account: Pubkey,
+    allow_further_share_creation: bool,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_mint, false),
+            AccountMeta::new(redeem_treasury, false),
+            AccountMeta::new(fraction_treasury, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(vault_authority, false),
+            AccountMeta::new_readonly(external_pricing_account, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: VaultInstruction::InitVault(InitVaultArgs { allow_further_share_creation })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_add_token_to_inactive_vault_instruction(
+    program_id: Pubkey,
+    safety_deposit_box: Pubkey,
+    token_account: Pubkey,
+    store: Pubkey,
+    vault: Pubkey,
+    vault_authority: Pubkey,
+    payer: Pubkey,
+    transfer_authority: Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(safety_deposit_box, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(store, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(vault_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(transfer_authority, true),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: VaultInstruction::AddTokenToInactiveVault(AmountArgs { amount })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_activate_vault_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    fraction_mint: Pubkey,
+    fraction_treasury: Pubkey,
+    fraction_mint_authority: Pubkey,
+    vault_authority: Pubkey,
+    number_of_shares: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new(fraction_mint, false),
+            AccountMeta::new(fraction_treasury, false),
+            AccountMeta::new_readonly(fraction_mint_authority, false),
+            AccountMeta::new_readonly(vault_authority, true),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: VaultInstruction::ActivateVault(NumberOfShareArgs { number_of_shares })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_mint_fractional_shares_instruction(
+    program_id: Pubkey,
+    fraction_treasury: Pubkey,
+    fraction_mint: Pubkey,
+    vault: Pubkey,
+    fraction_mint_authority: Pubkey,
+    vault_authority: Pubkey,
+    number_of_shares: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(fraction_treasury, false),
+            AccountMeta::new(fraction_mint, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(fraction_mint_authority, false),
+            AccountMeta::new_readonly(vault_authority, true),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: VaultInstruction::MintFractionalShares(NumberOfShareArgs { number_of_shares })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_withdraw_shares_from_treasury_instruction(
+    program_id: Pubkey,
+    destination: Pubkey,
+    fraction_treasury: Pubkey,
+    vault: Pubkey,
+    transfer_authority: Pubkey,
+    vault_authority: Pubkey,
+    number_of_shares: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(destination, false),
+            AccountMeta::new(fraction_treasury, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(transfer_authority, false),
+            AccountMeta::new_readonly(vault_authority, true),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: VaultInstruction::WithdrawSharesFromTreasury(NumberOfShareArgs { number_of_shares })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_add_shares_to_treasury_instruction(
+    program_id: Pubkey,
+    source: Pubkey,
+    fraction_treasury: Pubkey,
+    vault: Pubkey,
+    transfer_authority: Pubkey,
+    vault_authority: Pubkey,
+    number_of_shares: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(source, false),
+            AccountMeta::new(fraction_treasury, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(transfer_authority, true),
+            AccountMeta::new_readonly(vault_authority, true),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: VaultInstruction::AddSharesToTreasury(NumberOfShareArgs { number_of_shares })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_update_external_price_account_instruction(
+    program_id: Pubkey,
+    external_pricing_account: Pubkey,
+    price: ExternalPriceAccountArgs,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(external_pricing_account, true),
+        ],
+        data: VaultInstruction::UpdateExternalPriceAccount(price)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_set_authority_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    vault_authority: Pubkey,
+    new_authority: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(vault_authority, true),
+            AccountMeta::new_readonly(new_authority, false),
+        ],
+        data: VaultInstruction::SetAuthority.try_to_vec().unwrap(),
+    }
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct InitVaultArgs {
@@ -215,6 +493,17 @@ pub struct NumberOfShareArgs {
     pub number_of_shares: u64,
 }
 
+// The external price account as laid out by the token vault program, unlike
+// `Price` which also carries the discriminator of the price tracker account.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ExternalPriceAccountArgs {
+    pub key: Key,
+    pub price_per_share: u64,
+    pub price_mint: Pubkey,
+    pub allowed_to_combine: bool,
+}
+
 /// Instructions supported by the Fraction program.
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub enum VaultInstruction {
@@ -227,6 +516,32 @@ pub enum VaultInstruction {
     MintFractionalShares(NumberOfShareArgs),
     WithdrawSharesFromTreasury(NumberOfShareArgs),
     AddSharesToTreasury(NumberOfShareArgs),
-    UpdateExternalPriceAccount(Price),
+    UpdateExternalPriceAccount(ExternalPriceAccountArgs),
     SetAuthority,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_external_price_account_matches_token_vault_layout() {
+        let price_mint = Pubkey::new_unique();
+        let ix = create_update_external_price_account_instruction(
+            token_vault_program::ID,
+            Pubkey::new_unique(),
+            ExternalPriceAccountArgs {
+                key: Key::ExternalAccountKeyV1,
+                price_per_share: 5,
+                price_mint,
+                allowed_to_combine: true,
+            }
+        );
+
+        let mut expected = vec![9, Key::ExternalAccountKeyV1 as u8];
+        expected.extend_from_slice(&5u64.to_le_bytes());
+        expected.extend_from_slice(price_mint.as_ref());
+        expected.push(1);
+        assert_eq!(ix.data, expected);
+    }
 }
\ No newline at end of file
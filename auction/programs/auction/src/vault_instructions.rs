@@ -0,0 +1,433 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+
+use crate::vault::{
+    token_vault_program,
+    ExternalPriceAccountArgs,
+    create_init_vault_instruction,
+    create_add_token_to_inactive_vault_instruction,
+    create_activate_vault_instruction,
+    create_combine_vault_instruction,
+    create_redeem_shares_instruction,
+    create_withdraw_tokens_instruction,
+    create_mint_fractional_shares_instruction,
+    create_withdraw_shares_from_treasury_instruction,
+    create_add_shares_to_treasury_instruction,
+    create_update_external_price_account_instruction,
+    create_set_authority_instruction,
+};
+
+// Anchor style bindings for invoking the token vault program. Each accounts
+// struct lists the accounts in the order the token vault expects them, the
+// PDA signing for the vault's mint and token accounts is always `vault_pda`.
+
+#[derive(Accounts)]
+pub struct InitVault<'info> {
+    pub fraction_mint: AccountInfo<'info>,
+    pub redeem_treasury: AccountInfo<'info>,
+    pub fraction_treasury: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub external_pricing_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>
+}
+
+pub fn init_vault<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, InitVault<'info>>, allow_further_share_creation: bool) -> ProgramResult {
+    let ix = create_init_vault_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.fraction_mint.key,
+        *ctx.accounts.redeem_treasury.key,
+        *ctx.accounts.fraction_treasury.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.vault_authority.key,
+        *ctx.accounts.external_pricing_account.key,
+        allow_further_share_creation,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.fraction_mint.clone(),
+            ctx.accounts.redeem_treasury.clone(),
+            ctx.accounts.fraction_treasury.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.external_pricing_account.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.accounts.rent.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct AddTokenToInactiveVault<'info> {
+    pub safety_deposit_box: AccountInfo<'info>,
+    pub token_account: AccountInfo<'info>,
+    pub store: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub transfer_authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>
+}
+
+pub fn add_token_to_inactive_vault<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, AddTokenToInactiveVault<'info>>, amount: u64) -> ProgramResult {
+    let ix = create_add_token_to_inactive_vault_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.safety_deposit_box.key,
+        *ctx.accounts.token_account.key,
+        *ctx.accounts.store.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.vault_authority.key,
+        *ctx.accounts.payer.key,
+        *ctx.accounts.transfer_authority.key,
+        amount,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.safety_deposit_box.clone(),
+            ctx.accounts.token_account.clone(),
+            ctx.accounts.store.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.payer.clone(),
+            ctx.accounts.transfer_authority.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.accounts.rent.clone(),
+            ctx.accounts.system_program.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct ActivateVault<'info> {
+    pub vault: AccountInfo<'info>,
+    pub fraction_mint: AccountInfo<'info>,
+    pub fraction_treasury: AccountInfo<'info>,
+    pub vault_pda: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>
+}
+
+pub fn activate_vault<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, ActivateVault<'info>>, number_of_shares: u64) -> ProgramResult {
+    let ix = create_activate_vault_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.fraction_mint.key,
+        *ctx.accounts.fraction_treasury.key,
+        *ctx.accounts.vault_pda.key,
+        *ctx.accounts.vault_authority.key,
+        number_of_shares,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.vault.clone(),
+            ctx.accounts.fraction_mint.clone(),
+            ctx.accounts.fraction_treasury.clone(),
+            ctx.accounts.vault_pda.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct CombineVault<'info> {
+    pub vault: AccountInfo<'info>,
+    pub outstanding_share_token_account: AccountInfo<'info>,
+    pub paying_token_account: AccountInfo<'info>,
+    pub fraction_mint: AccountInfo<'info>,
+    pub fraction_treasury: AccountInfo<'info>,
+    pub redeem_treasury: AccountInfo<'info>,
+    pub new_vault_authority: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub paying_transfer_authority: AccountInfo<'info>,
+    pub vault_pda: AccountInfo<'info>,
+    pub external_pricing_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>
+}
+
+pub fn combine_vault<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, CombineVault<'info>>) -> ProgramResult {
+    let ix = create_combine_vault_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.outstanding_share_token_account.key,
+        *ctx.accounts.paying_token_account.key,
+        *ctx.accounts.fraction_mint.key,
+        *ctx.accounts.fraction_treasury.key,
+        *ctx.accounts.redeem_treasury.key,
+        *ctx.accounts.new_vault_authority.key,
+        *ctx.accounts.vault_authority.key,
+        *ctx.accounts.paying_transfer_authority.key,
+        *ctx.accounts.vault_pda.key,
+        *ctx.accounts.external_pricing_account.key,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.vault.clone(),
+            ctx.accounts.outstanding_share_token_account.clone(),
+            ctx.accounts.paying_token_account.clone(),
+            ctx.accounts.fraction_mint.clone(),
+            ctx.accounts.fraction_treasury.clone(),
+            ctx.accounts.redeem_treasury.clone(),
+            ctx.accounts.new_vault_authority.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.paying_transfer_authority.clone(),
+            ctx.accounts.vault_pda.clone(),
+            ctx.accounts.external_pricing_account.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct RedeemShares<'info> {
+    pub outstanding_shares_account: AccountInfo<'info>,
+    pub proceeds_account: AccountInfo<'info>,
+    pub fraction_mint: AccountInfo<'info>,
+    pub redeem_treasury: AccountInfo<'info>,
+    pub vault_pda: AccountInfo<'info>,
+    pub burn_authority: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>
+}
+
+pub fn redeem_shares<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, RedeemShares<'info>>) -> ProgramResult {
+    let ix = create_redeem_shares_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.outstanding_shares_account.key,
+        *ctx.accounts.proceeds_account.key,
+        *ctx.accounts.fraction_mint.key,
+        *ctx.accounts.redeem_treasury.key,
+        *ctx.accounts.vault_pda.key,
+        *ctx.accounts.burn_authority.key,
+        *ctx.accounts.vault.key,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.outstanding_shares_account.clone(),
+            ctx.accounts.proceeds_account.clone(),
+            ctx.accounts.fraction_mint.clone(),
+            ctx.accounts.redeem_treasury.clone(),
+            ctx.accounts.vault_pda.clone(),
+            ctx.accounts.burn_authority.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.accounts.rent.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTokenFromSafetyDepositBox<'info> {
+    pub destination_token_account: AccountInfo<'info>,
+    pub safety_deposit_box: AccountInfo<'info>,
+    pub locked_token_account: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub fraction_mint: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub vault_pda: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>
+}
+
+pub fn withdraw_token_from_safety_deposit_box<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawTokenFromSafetyDepositBox<'info>>, amount: u64) -> ProgramResult {
+    let ix = create_withdraw_tokens_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.destination_token_account.key,
+        *ctx.accounts.safety_deposit_box.key,
+        *ctx.accounts.locked_token_account.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.fraction_mint.key,
+        *ctx.accounts.vault_authority.key,
+        *ctx.accounts.vault_pda.key,
+        amount,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.destination_token_account.clone(),
+            ctx.accounts.safety_deposit_box.clone(),
+            ctx.accounts.locked_token_account.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.fraction_mint.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.vault_pda.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.accounts.rent.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct MintFractionalShares<'info> {
+    pub fraction_treasury: AccountInfo<'info>,
+    pub fraction_mint: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub vault_pda: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>
+}
+
+pub fn mint_fractional_shares<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, MintFractionalShares<'info>>, number_of_shares: u64) -> ProgramResult {
+    let ix = create_mint_fractional_shares_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.fraction_treasury.key,
+        *ctx.accounts.fraction_mint.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.vault_pda.key,
+        *ctx.accounts.vault_authority.key,
+        number_of_shares,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.fraction_treasury.clone(),
+            ctx.accounts.fraction_mint.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.vault_pda.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSharesFromTreasury<'info> {
+    pub destination: AccountInfo<'info>,
+    pub fraction_treasury: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub vault_pda: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>
+}
+
+pub fn withdraw_shares_from_treasury<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawSharesFromTreasury<'info>>, number_of_shares: u64) -> ProgramResult {
+    let ix = create_withdraw_shares_from_treasury_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.destination.key,
+        *ctx.accounts.fraction_treasury.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.vault_pda.key,
+        *ctx.accounts.vault_authority.key,
+        number_of_shares,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.destination.clone(),
+            ctx.accounts.fraction_treasury.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.vault_pda.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.accounts.rent.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct AddSharesToTreasury<'info> {
+    pub source: AccountInfo<'info>,
+    pub fraction_treasury: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub transfer_authority: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>
+}
+
+pub fn add_shares_to_treasury<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, AddSharesToTreasury<'info>>, number_of_shares: u64) -> ProgramResult {
+    let ix = create_add_shares_to_treasury_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.source.key,
+        *ctx.accounts.fraction_treasury.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.transfer_authority.key,
+        *ctx.accounts.vault_authority.key,
+        number_of_shares,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.source.clone(),
+            ctx.accounts.fraction_treasury.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.transfer_authority.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct UpdateExternalPriceAccount<'info> {
+    pub external_pricing_account: AccountInfo<'info>
+}
+
+pub fn update_external_price_account<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, UpdateExternalPriceAccount<'info>>, price: ExternalPriceAccountArgs) -> ProgramResult {
+    let ix = create_update_external_price_account_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.external_pricing_account.key,
+        price,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.external_pricing_account.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
+
+#[derive(Accounts)]
+pub struct SetVaultAuthority<'info> {
+    pub vault: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub new_vault_authority: AccountInfo<'info>
+}
+
+pub fn set_vault_authority<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, SetVaultAuthority<'info>>) -> ProgramResult {
+    let ix = create_set_authority_instruction(
+        token_vault_program::ID,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.vault_authority.key,
+        *ctx.accounts.new_vault_authority.key,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.vault.clone(),
+            ctx.accounts.vault_authority.clone(),
+            ctx.accounts.new_vault_authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
@@ -5,6 +5,7 @@ const { PublicKey, SYSVAR_RENT_PUBKEY, SystemProgram, Keypair, Transaction } = a
 const vaultProgram = require("./vault/plain");
 
 const VAULT_PROGRAM_ID = new PublicKey("FMcRJeEKBjD1LU5UyBHvorpep4hVg48ff2C36NX83vtY");
+const PRICE_PROGRAM_ID = new PublicKey("D8K3mcxvwDE7ykDPhL6xGXfkWXhbB1YS2nH3MCS1WmsD");
 
 const getTokenAccountBalance = async (connection, address) => {
 	return parseInt((await connection.getTokenAccountBalance(address)).value.amount);
@@ -64,6 +65,27 @@ const findBidAccount = async (_program, _auctionAccount, _bidder) => {
 	);
 };
 
+const findPricePDA = async (_priceAccount) => {
+	return await PublicKey.findProgramAddress(
+		[Buffer.from("price"), _priceAccount.toBuffer(), PRICE_PROGRAM_ID.toBuffer()], 
+		PRICE_PROGRAM_ID
+	);
+};
+
+const findProgramAccount = async (_program, _seeds) => {
+	return await PublicKey.findProgramAddress(
+		_seeds.map(seed => seed instanceof PublicKey ? seed.toBuffer() : Buffer.from(seed)), 
+		_program.programId
+	);
+};
+
+const findTemporaryTokenAccount = async (_program, _payer) => {
+	return await PublicKey.findProgramAddress(
+		[Buffer.from("unwrap"), _payer.toBuffer()], 
+		_program.programId
+	);
+};
+
 const findSafetyBox = async (_vaultAccount, _mint) => {
 	return PublicKey.findProgramAddress(
 		[Buffer.from("vault"), _vaultAccount.toBuffer(), _mint.toBuffer()], 
@@ -89,6 +111,28 @@ describe('auction', () => {
 	var lockedMint;
 	var storeAccount;
 	var destAccount;
+	var lockedMints;
+
+	// Variables for the fractionalized vault
+	var curatedVault;
+	var curatedPriceAccount;
+	var curation;
+
+	// Variables for the listing
+	var listedVault;
+	var listedPricingAccount;
+	var listedFractionTreasury;
+	var listedRedeemTreasury;
+	var listedFractionMint;
+	var listedLockedMint;
+	var listing;
+	var listingTreasury;
+
+	// Variables for the standing offer
+	var offered;
+	var offerBidder;
+	var offer;
+	var offerAuthority;
 
 	// Variables for the auction
 	var settings;
@@ -102,10 +146,11 @@ describe('auction', () => {
 
 	
 	// Variables for the settings
-	const duration = new anchor.BN(10);
+	const duration = new anchor.BN(30);
 	const softClose = new anchor.BN(0);
 	const bidIncrement = new anchor.BN(1e8);
 	const facilitatorFee = new anchor.BN(0);
+	const offerWarmUp = new anchor.BN(1);
 
 	const start = async (bidAmount, payAmount, user) => {
 
@@ -118,12 +163,13 @@ describe('auction', () => {
 		// Find the auction and bid accounts
 		let [auction, auctionBump] = await findAuctionAccount(program, settings, vault);
 		let [bid, bidBump] = await findBidAccount(program, auction, user.publicKey);
+		let [fractionEscrow, escrowBump] = await findProgramAccount(program, ["escrow", bid]);
 
 		// Start the auction
 		let bidTokenAccountKeypair = Keypair.generate();
 		let bidTokenAccount = bidTokenAccountKeypair.publicKey;
 
-		await program.rpc.start(auctionBump, bidBump, new anchor.BN(bidAmount * 1e9), {
+		await program.rpc.start(auctionBump, bidBump, escrowBump, new anchor.BN(bidAmount * 1e9), new anchor.BN(0), null, {
 			accounts: {
 				authority,
 				auction,
@@ -137,13 +183,18 @@ describe('auction', () => {
 				paymentTreasury,
 				bid,
 				bidTokenAccount,
+				fractionEscrow,
 				payingTokenAccount,
+				// No fractions are escrowed with the opening bid
+				payingFractionAccount: outstandingFractionsTokenAccount,
 				outstandingFractionsTokenAccount,
 
 				paymentMint: NATIVE_MINT,
 				fractionMint,
 			
-				bidder: user.publicKey,
+				payer: user.publicKey,
+				fundingAuthority: user.publicKey,
+				beneficiary: user.publicKey,
 				tokenProgram: TOKEN_PROGRAM_ID,
 				rent: SYSVAR_RENT_PUBKEY,
 				systemProgram: SystemProgram.programId,
@@ -170,21 +221,32 @@ describe('auction', () => {
 		return user;
 	}
 
-	const bid = async (_user, _amount) => {
-		let payingTokenAccount = await createNativeTokenAccount(provider, _amount, _user);
+	// Bids are paid by `_funder` from a wrapped SOL token account, or straight from their wallet if `_native`.
+	// Outbid bids are refunded to the registered `_refundAccount`, or else to the bidder's wallet.
+	// Any `_fractions` are escrowed from the payer's fraction account, so only the payer may escrow them.
+	// The outbid bid's refund account can be overridden with `_topBidRefundAccount`.
+	const bid = async (_user, _amount, _native = false, _refundAccount = null, _funder = _user, _fractions = 0, _topBidRefundAccount = null) => {
+		let payingTokenAccount = _native ? _funder.publicKey : await createNativeTokenAccount(provider, _amount, _funder);
+		let payingFractionAccount = _fractions > 0 ? destAccount : _funder.publicKey;
 		let auction = (await findAuctionAccount(program, settings, vault))[0];
 		let [bid, bidBump] = await findBidAccount(program, auction, _user.publicKey);
+		let [fractionEscrow, escrowBump] = await findProgramAccount(program, ["escrow", bid]);
 		let bidTokenAccountKeypair = Keypair.generate();
 		let bidTokenAccount = bidTokenAccountKeypair.publicKey;
 		let [topBid, topBidTokenAccount] = await topBidInfo(auction);
 		let topBidData = await program.account.bid.fetch(topBid);
+		let topBidder = topBidData.bidder;
+		let topBidRefundAccount = _topBidRefundAccount || topBidData.refundAccount || topBidder;
+		let topBidFractionEscrow = (await findProgramAccount(program, ["escrow", topBid]))[0];
+		let temporaryTokenAccount = (await findTemporaryTokenAccount(program, _funder.publicKey))[0];
 
 		let payingAccountBalanceBefore = await connection.getBalance(payingTokenAccount);
 		let paymentTreasuryBalanceBefore = await connection.getBalance(paymentTreasury);
 		let topBidAccountBalanceBefore = await connection.getBalance(topBidTokenAccount);
+		let topBidRefundAccountBalanceBefore = await connection.getBalance(topBidRefundAccount);
 		let paymentAmount = new anchor.BN(1e9 * _amount);
 
-		await program.rpc.placeBid(bidBump, paymentAmount, {
+		await program.rpc.placeBid(bidBump, escrowBump, paymentAmount, new anchor.BN(_fractions), _refundAccount, {
 			accounts: {
 				auction,
 				settings,
@@ -192,25 +254,58 @@ describe('auction', () => {
 				paymentTreasury,
 				topBid,
 				topBidTokenAccount,
+				topBidder,
+				topBidRefundAccount,
+				topBidFractionEscrow,
+				topBidFractionAccount: topBidData.fractionAccount,
 				bid,
 				bidTokenAccount,
+				fractionEscrow,
 				paymentMint: NATIVE_MINT,
+				fractionMint,
+				vaultFractionTreasury,
 				payingTokenAccount,
-				bidder: _user.publicKey,
+				payingFractionAccount,
+				payer: _funder.publicKey,
+				fundingAuthority: _funder.publicKey,
+				beneficiary: _user.publicKey,
+				temporaryTokenAccount,
 				tokenProgram: TOKEN_PROGRAM_ID,
 				rent: SYSVAR_RENT_PUBKEY,
 				systemProgram: SystemProgram.programId,
 			},
-			signers: [bidTokenAccountKeypair, _user]
+			signers: [bidTokenAccountKeypair, _funder]
 		});
 
+		// Only the part of the bid which is not credited for escrowed fractions is paid
+		let bidAccount = await program.account.bid.fetch(bid);
+		let payment = paymentAmount.toNumber() - bidAccount.fractionCredit.toNumber();
+		let topBidPayment = topBidData.amount.toNumber() - topBidData.fractionCredit.toNumber();
+		assert.equal(bidAccount.escrowedFractions.toNumber(), _fractions);
+		assert.equal(await getTokenAccountBalance(connection, fractionEscrow), _fractions);
+
 		let payingAccountBalanceAfter = await connection.getBalance(payingTokenAccount);
 		let paymentTreasuryBalanceAfter = await connection.getBalance(paymentTreasury);
-		let topBidAccountBalanceAfter = await connection.getBalance(topBidTokenAccount);
-		let bidIncrease = paymentAmount.toNumber() - topBidData.amount.toNumber() 
-		assert.equal(payingAccountBalanceBefore - payingAccountBalanceAfter, paymentAmount.toNumber());
-		assert.equal(paymentTreasuryBalanceAfter - paymentTreasuryBalanceBefore, bidIncrease);
-		assert.equal(topBidAccountBalanceAfter - topBidAccountBalanceBefore, topBidData.amount.toNumber() );
+		assert.equal(paymentTreasuryBalanceAfter - paymentTreasuryBalanceBefore, payment - topBidPayment);
+
+		// The bidder's wallet also pays the rent for the new bid accounts
+		if (!_native) {
+			assert.equal(payingAccountBalanceBefore - payingAccountBalanceAfter, payment);
+		}
+
+		let topBidAccountInfo = await connection.getAccountInfo(topBid);
+		if (topBidAccountInfo === null) {
+			// The outbid amount has been pushed to the refund account and the old bid closed
+			let topBidRefundAccountBalanceAfter = await connection.getBalance(topBidRefundAccount);
+			assert.isNull(await connection.getAccountInfo(topBidTokenAccount));
+			if (!topBidRefundAccount.equals(payer.publicKey)) {
+				assert.isAtLeast(topBidRefundAccountBalanceAfter - topBidRefundAccountBalanceBefore, topBidPayment);
+			}
+		} else {
+			// The outbid amount is held in the old bid token account until it is withdrawn
+			let topBidAccountBalanceAfter = await connection.getBalance(topBidTokenAccount);
+			assert.equal(topBidAccountBalanceAfter - topBidAccountBalanceBefore, topBidPayment);
+		}
 
 	}
 
@@ -222,6 +317,7 @@ describe('auction', () => {
 		let bidTokenAccount = bidAccount.tokenAccount;
 
 		let toAccountBalanceBefore = await connection.getBalance(toAccount);
+		let bidTokenAccountBalance = await getTokenAccountBalance(connection, bidTokenAccount);
 
 		await program.rpc.withdrawBid({
 			accounts: {
@@ -230,15 +326,190 @@ describe('auction', () => {
 				vault,
 				bid,
 				bidTokenAccount,
+				fractionEscrow: (await findProgramAccount(program, ["escrow", bid]))[0],
 				toAccount,
-				bidder: _user.publicKey,
+				fractionToAccount: bidAccount.fractionAccount,
+				beneficiary: _user.publicKey,
 				tokenProgram: TOKEN_PROGRAM_ID,
 			},
 			signers: [_user]
 		});
 
 		let toAccountBalanceAfter = await connection.getBalance(toAccount);
-		assert.equal(toAccountBalanceAfter - toAccountBalanceBefore, bidAccount.amount.toNumber());
+		assert.equal(toAccountBalanceAfter - toAccountBalanceBefore, bidTokenAccountBalance);
+		assert.isNull(await connection.getAccountInfo(bidTokenAccount));
+	}
+
+	const withdrawBids = async (_user) => {
+		let toAccount = await createNativeTokenAccount(provider, 0, _user);
+		let auction = (await findAuctionAccount(program, settings, vault))[0];
+		let bid = (await findBidAccount(program, auction, _user.publicKey))[0];
+		let bidAccount = await program.account.bid.fetch(bid);
+		let bidTokenAccount = bidAccount.tokenAccount;
+		let bidTokenAccountBalance = await getTokenAccountBalance(connection, bidTokenAccount);
+		let fractionEscrow = (await findProgramAccount(program, ["escrow", bid]))[0];
+
+		// The rent of every closed bidding account goes back to the beneficiary
+		let rent = 0;
+		for (let account of [bid, bidTokenAccount, fractionEscrow]) {
+			rent += (await connection.getAccountInfo(account)).lamports;
+		}
+		let beneficiaryBalanceBefore = await connection.getBalance(_user.publicKey);
+
+		await program.rpc.withdrawBids({
+			accounts: {
+				toAccount,
+				beneficiary: _user.publicKey,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts: [
+				{ pubkey: auction, isWritable: false, isSigner: false },
+				{ pubkey: bid, isWritable: true, isSigner: false },
+				{ pubkey: bidTokenAccount, isWritable: true, isSigner: false },
+				{ pubkey: fractionEscrow, isWritable: true, isSigner: false },
+				{ pubkey: bidAccount.fractionAccount, isWritable: true, isSigner: false },
+			],
+			signers: [_user]
+		});
+
+		assert.equal(await getTokenAccountBalance(connection, toAccount), bidTokenAccountBalance);
+		assert.isNull(await connection.getAccountInfo(bid));
+		assert.isNull(await connection.getAccountInfo(bidTokenAccount));
+		assert.isNull(await connection.getAccountInfo(fractionEscrow));
+		assert.equal(await connection.getBalance(_user.publicKey) - beneficiaryBalanceBefore, rent);
+	}
+
+	const createPool = async () => {
+		let auction = (await findAuctionAccount(program, settings, vault))[0];
+		let [pool, poolBump] = await findProgramAccount(program, ["pool", auction]);
+		let poolTokenAccountKeypair = Keypair.generate();
+
+		await program.rpc.createPool(poolBump, {
+			accounts: {
+				auction,
+				pool,
+				poolTokenAccount: poolTokenAccountKeypair.publicKey,
+				paymentMint: NATIVE_MINT,
+				payer: payer.publicKey,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [poolTokenAccountKeypair]
+		});
+
+		return pool;
+	}
+
+	// Contributions are paid in native SOL straight from the contributor's wallet.
+	const contribute = async (_pool, _user, _amount) => {
+		let poolAccount = await program.account.pool.fetch(_pool);
+		let [contribution, contributionBump] = await findProgramAccount(program, ["contribution", _pool, _user.publicKey]);
+
+		await program.rpc.joinPool(contributionBump, {
+			accounts: {
+				pool: _pool,
+				contribution,
+				contributor: _user.publicKey,
+				systemProgram: SystemProgram.programId,
+			},
+			signers: [_user]
+		});
+
+		await program.rpc.contribute(new anchor.BN(_amount * 1e9), {
+			accounts: {
+				auction: poolAccount.auction,
+				pool: _pool,
+				poolTokenAccount: poolAccount.tokenAccount,
+				contribution,
+				payingTokenAccount: _user.publicKey,
+				contributor: _user.publicKey,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+			},
+			signers: [_user]
+		});
+
+		assert.equal((await program.account.contribution.fetch(contribution)).amount.toNumber(), _amount * 1e9);
+	}
+
+	const poolBid = async (_pool, _amount) => {
+		let poolAccount = await program.account.pool.fetch(_pool);
+		let auction = poolAccount.auction;
+		let [bid, bidBump] = await findBidAccount(program, auction, _pool);
+		let [fractionEscrow, escrowBump] = await findProgramAccount(program, ["escrow", bid]);
+		let bidTokenAccountKeypair = Keypair.generate();
+		let [topBid, topBidTokenAccount] = await topBidInfo(auction);
+		let topBidData = await program.account.bid.fetch(topBid);
+
+		await program.rpc.poolBid(bidBump, escrowBump, new anchor.BN(_amount * 1e9), {
+			accounts: {
+				pool: _pool,
+				poolTokenAccount: poolAccount.tokenAccount,
+				auction,
+				settings,
+				vault,
+				paymentTreasury,
+				topBid,
+				topBidTokenAccount,
+				topBidder: topBidData.bidder,
+				topBidRefundAccount: topBidData.refundAccount || topBidData.bidder,
+				topBidFractionEscrow: (await findProgramAccount(program, ["escrow", topBid]))[0],
+				topBidFractionAccount: topBidData.fractionAccount,
+				bid,
+				bidTokenAccount: bidTokenAccountKeypair.publicKey,
+				fractionEscrow,
+				paymentMint: NATIVE_MINT,
+				fractionMint,
+				vaultFractionTreasury,
+				payer: payer.publicKey,
+				temporaryTokenAccount: (await findTemporaryTokenAccount(program, payer.publicKey))[0],
+				auctionProgram: program.programId,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [bidTokenAccountKeypair]
+		});
+
+		let bidAccount = await program.account.bid.fetch(bid);
+		assert.ok(bidAccount.bidder.equals(_pool));
+		assert.ok(bidAccount.refundAccount.equals(poolAccount.tokenAccount));
+	}
+
+	// Withdraws into a fresh wrapped SOL token account, or straight into the wallet if `_native`.
+	const withdrawContribution = async (_pool, _user, _native = false) => {
+		let poolAccount = await program.account.pool.fetch(_pool);
+		let contribution = (await findProgramAccount(program, ["contribution", _pool, _user.publicKey]))[0];
+		let contributionAccount = await program.account.contribution.fetch(contribution);
+		let toAccount = _native ? _user.publicKey : await createNativeTokenAccount(provider, 0, _user);
+
+		let poolBalance = await getTokenAccountBalance(connection, poolAccount.tokenAccount);
+		let share = Math.floor(poolBalance * contributionAccount.amount.toNumber() / poolAccount.totalContributions.toNumber());
+		let toAccountBalanceBefore = await connection.getBalance(toAccount);
+
+		await program.rpc.withdrawContribution({
+			accounts: {
+				auction: poolAccount.auction,
+				pool: _pool,
+				poolTokenAccount: poolAccount.tokenAccount,
+				contribution,
+				toAccount,
+				contributor: _user.publicKey,
+				paymentMint: NATIVE_MINT,
+				temporaryTokenAccount: (await findTemporaryTokenAccount(program, _user.publicKey))[0],
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [_user]
+		});
+
+		// The wallet also gets back the rent of the contribution account
+		let toAccountBalanceAfter = await connection.getBalance(toAccount);
+		assert.isAtLeast(toAccountBalanceAfter - toAccountBalanceBefore, share);
+		assert.equal(poolBalance - await getTokenAccountBalance(connection, poolAccount.tokenAccount), share);
+		assert.isNull(await connection.getAccountInfo(contribution));
 	}
 
 	const claim = async (_user) => {
@@ -260,7 +531,7 @@ describe('auction', () => {
 				settings,
 				vault,
 				destinationTokenAccount,
-				bidder: _user.publicKey,
+				beneficiary: _user.publicKey,
 				tokenProgram: TOKEN_PROGRAM_ID,
 				lockedTokenAccount,
 				safetyDepositBox,
@@ -278,6 +549,52 @@ describe('auction', () => {
 		assert.equal(lockedAccountBalanceBefore - lockedAccountBalanceAfter, 1);
 	}
 
+	// Empty the given safety deposit boxes into new accounts of the user in one go
+	const claimAll = async (_user, _safetyDepositBoxes, _accounts = {}) => {
+		let auction = (await findAuctionAccount(program, settings, vault))[0];
+		let vaultPda = (await findVaultPDA(vault))[0];
+
+		let remainingAccounts = [];
+		let lockedTokenAccounts = [];
+		let destinationTokenAccounts = [];
+		for (let safetyDepositBox of _safetyDepositBoxes) {
+			let safetyBoxAccount = await vaultProgram.decodeSafetyDepositBox(connection, safetyDepositBox);
+			let lockedTokenAccount = new PublicKey(safetyBoxAccount.data.store);
+			let lockedMintToken = new Token(connection, new PublicKey(safetyBoxAccount.data.tokenMint), TOKEN_PROGRAM_ID, _user);
+			let destinationTokenAccount = await lockedMintToken.createAccount(_user.publicKey);
+			remainingAccounts.push(
+				{ pubkey: safetyDepositBox, isWritable: false, isSigner: false },
+				{ pubkey: lockedTokenAccount, isWritable: true, isSigner: false },
+				{ pubkey: destinationTokenAccount, isWritable: true, isSigner: false },
+			);
+			lockedTokenAccounts.push(lockedTokenAccount);
+			destinationTokenAccounts.push(destinationTokenAccount);
+		}
+
+		await program.rpc.claimAll({
+			accounts: {
+				authority,
+				auction,
+				settings,
+				vault,
+				beneficiary: _user.publicKey,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				vaultPda,
+				vaultProgram: VAULT_PROGRAM_ID,
+				fractionMint,
+				rent: SYSVAR_RENT_PUBKEY,
+				..._accounts
+			},
+			remainingAccounts,
+			signers: [_user]
+		});
+
+		for (let i = 0; i < _safetyDepositBoxes.length; i++) {
+			assert.equal(await getTokenAccountBalance(connection, lockedTokenAccounts[i]), 0);
+			assert.equal(await getTokenAccountBalance(connection, destinationTokenAccounts[i]), 1);
+		}
+	}
+
 	const redeem = async (_user) => {
 		let destinationTokenAccount = await createNativeTokenAccount(provider, 0, _user);
 		let auction = (await findAuctionAccount(program, settings, vault))[0];
@@ -286,6 +603,7 @@ describe('auction', () => {
 		let paymentTreasuryBalanceBefore = await connection.getBalance(paymentTreasury);
 		let destAccountBalanceBefore = await connection.getBalance(destinationTokenAccount);
 		let redeemTreasuryBalanceBefore = await connection.getBalance(vaultRedeemTreasury);
+		let temporaryTokenAccount = (await findTemporaryTokenAccount(program, _user.publicKey))[0];
 
 		await program.rpc.redeem({
 			accounts: {
@@ -300,6 +618,8 @@ describe('auction', () => {
 				vaultPda,
 				vaultProgram: VAULT_PROGRAM_ID,
 				fractionMint,
+				paymentMint: NATIVE_MINT,
+				temporaryTokenAccount,
 				tokenProgram: TOKEN_PROGRAM_ID,
 				rent: SYSVAR_RENT_PUBKEY,
 				systemProgram: SystemProgram.programId,
@@ -355,16 +675,166 @@ describe('auction', () => {
 			fractionMint, 
 			lockedMint, 
 			storeAccount, 
-			destAccount
-		] = await vaultProgram.createVault(provider, authority);
+			destAccount,
+			lockedMints
+		] = await vaultProgram.createVault(provider, authority, 3);
 	})
 
+	// Lock a new NFT in a vault fractionalized through the program, with the payer as curator
+	const fractionalizeNft = async (_decimals = 9) => {
+
+		let nftMint = await Token.createMint(connection, payer, payer.publicKey, null, 0, TOKEN_PROGRAM_ID);
+		let curatorNftAccount = await nftMint.createAccount(payer.publicKey);
+		await nftMint.mintTo(curatorNftAccount, payer.publicKey, [], 1);
+
+		let vaultKeypair = Keypair.generate();
+		let newVault = vaultKeypair.publicKey;
+		let priceAccountKeypair = Keypair.generate();
+		let priceStoreKeypair = Keypair.generate();
+		let [pricePda, pricePdaBump] = await findPricePDA(priceAccountKeypair.publicKey);
+
+		// The price tracker mints receipts for staked fractions, so it must be the mint authority
+		let lockedMintToken = await Token.createMint(connection, payer, pricePda, null, 9, TOKEN_PROGRAM_ID);
+
+		let [newCuration, curationBump] = await findProgramAccount(program, ["curation", newVault]);
+		let [newFractionMint, fractionMintBump] = await findProgramAccount(program, ["fraction_mint", newVault]);
+		let [fractionTreasury, fractionTreasuryBump] = await findProgramAccount(program, ["fraction_treasury", newVault]);
+		let [redeemTreasury, redeemTreasuryBump] = await findProgramAccount(program, ["redeem_treasury", newVault]);
+		let [curatorFractionAccount, curatorFractionsBump] = await findProgramAccount(program, ["fractions", newVault, payer.publicKey]);
+		let [store, storeBump] = await findProgramAccount(program, ["store", newVault, nftMint.publicKey]);
+		let safetyDepositBox = (await findSafetyBox(newVault, nftMint.publicKey))[0];
+		let vaultPda = (await findVaultPDA(newVault))[0];
+
+		let bumps = {
+			curation: curationBump,
+			fractionMint: fractionMintBump,
+			fractionTreasury: fractionTreasuryBump,
+			redeemTreasury: redeemTreasuryBump,
+			store: storeBump,
+			curatorFractions: curatorFractionsBump,
+			pricePda: pricePdaBump,
+		};
+
+		// A simple majority, voting within 1/5th and 5x of the current weighted average price,
+		// with votes counting straight away
+		let voteSettings = {
+			quorum: new anchor.BN(5_000),
+			minBand: new anchor.BN(2_000),
+			maxBand: new anchor.BN(50_000),
+			priceMode: { mean: {} },
+			warmUpPeriod: new anchor.BN(0),
+			cooldownPeriod: new anchor.BN(0),
+		};
+		await program.rpc.fractionalize(bumps, new anchor.BN(1_000_000), _decimals, new anchor.BN(1), voteSettings, {
+			accounts: {
+				authority,
+				curation: newCuration,
+				curator: payer.publicKey,
+				vault: newVault,
+				fractionMint: newFractionMint,
+				fractionTreasury,
+				redeemTreasury,
+				paymentMint: NATIVE_MINT,
+				curatorFractionAccount,
+				nftMint: nftMint.publicKey,
+				curatorNftAccount,
+				store,
+				safetyDepositBox,
+				externalPricingAccount: priceAccountKeypair.publicKey,
+				priceStore: priceStoreKeypair.publicKey,
+				priceStoreAuthority: pricePda,
+				lockedMint: lockedMintToken.publicKey,
+				priceProgram: PRICE_PROGRAM_ID,
+				vaultPda,
+				vaultProgram: VAULT_PROGRAM_ID,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [vaultKeypair, priceAccountKeypair, priceStoreKeypair]
+		});
+
+		return {
+			vault: newVault,
+			curation: newCuration,
+			priceAccount: priceAccountKeypair.publicKey,
+			priceStore: priceStoreKeypair.publicKey,
+			pricePda,
+			pricePdaBump,
+			lockedMint: lockedMintToken.publicKey,
+			fractionMint: newFractionMint,
+			fractionTreasury,
+			redeemTreasury,
+			curatorFractionAccount,
+			curatorNftAccount,
+			store,
+		};
+
+	};
+
+	it('fractionalize an NFT in one instruction', async () => {
+
+		let fractionalized = await fractionalizeNft();
+		curation = fractionalized.curation;
+
+		// The NFT is locked away and the curator holds every fraction
+		assert.equal(await getTokenAccountBalance(connection, fractionalized.store), 1);
+		assert.equal(await getTokenAccountBalance(connection, fractionalized.curatorNftAccount), 0);
+		assert.equal(await getTokenAccountBalance(connection, fractionalized.curatorFractionAccount), 1_000_000);
+
+		let curationData = await program.account.curation.fetch(curation);
+		assert.deepEqual(curationData.curator, payer.publicKey);
+		assert.deepEqual(curationData.vault, fractionalized.vault);
+		assert.deepEqual(curationData.externalPricingAccount, fractionalized.priceAccount);
+
+		curatedVault = fractionalized.vault;
+		curatedPriceAccount = fractionalized.priceAccount;
+
+	});
+
+	it('fractions are minted with the decimals asked for', async () => {
+		let fractionalized = await fractionalizeNft(6);
+		let fractionMintToken = new Token(connection, fractionalized.fractionMint, TOKEN_PROGRAM_ID, payer);
+		assert.equal((await fractionMintToken.getMintInfo()).decimals, 6);
+		assert.equal(await getTokenAccountBalance(connection, fractionalized.curatorFractionAccount), 1_000_000);
+	});
+
+	const releaseVault = async (_curator) => {
+		await program.rpc.releaseVault({
+			accounts: {
+				authority,
+				curation,
+				curator: _curator.publicKey,
+				vault: curatedVault,
+				auction: (await findAuctionAccount(program, settings, curatedVault))[0],
+				externalPricingAccount: curatedPriceAccount,
+				vaultProgram: VAULT_PROGRAM_ID,
+			},
+			signers: [_curator]
+		});
+	};
+
+	it('only the curator can release a vault', async () => {
+		let user = await createUser(1);
+		let call = async () => await releaseVault(user);
+		await assertError(call);
+	});
+
+	it('curator can release a vault which has not been auctioned', async () => {
+		await releaseVault(payer);
+		let vaultAccount = await connection.getAccountInfo(curatedVault);
+		// The vault authority follows the key byte, the token program and the fraction mint
+		let vaultAuthority = new PublicKey(vaultAccount.data.slice(1 + 2 * 32, 1 + 3 * 32));
+		assert.deepEqual(vaultAuthority, payer.publicKey);
+		assert.isNull(await connection.getAccountInfo(curation));
+	});
+
 	it('create settings', async () => {
 
 		// Create some new auction settings
 		const keypair = Keypair.generate();
 		settings = keypair.publicKey;
-		await program.rpc.createSettings(duration, softClose, bidIncrement, facilitatorFee, {
+		await program.rpc.createSettings(duration, softClose, bidIncrement, facilitatorFee, offerWarmUp, {
 			accounts: {
 				authority,
 				settings,
@@ -381,6 +851,7 @@ describe('auction', () => {
 		assert.isTrue(settingsData.softClosePeriod.eq(softClose));
 		assert.isTrue(settingsData.bidIncrement.eq(bidIncrement));
 		assert.isTrue(settingsData.facilitatorFee.eq(facilitatorFee));
+		assert.isTrue(settingsData.offerWarmUpPeriod.eq(offerWarmUp));
 
 		// console.log("VAULT:", vault.toString());
 		// console.log("SETTINGS:", settings.toString());
@@ -392,7 +863,7 @@ describe('auction', () => {
 		let keypair = Keypair.generate();
 		let user = await createUser(1);
 		let call = async () => {
-			await program.rpc.createSettings(duration, softClose, bidIncrement, facilitatorFee, {
+			await program.rpc.createSettings(duration, softClose, bidIncrement, facilitatorFee, offerWarmUp, {
 				accounts: {
 					authority,
 					settings: keypair.publicKey,
@@ -408,6 +879,397 @@ describe('auction', () => {
 
 	});
 
+	it('trust and distrust price programs', async () => {
+
+		let priceProgram = Keypair.generate().publicKey;
+		await program.rpc.addPriceProgram(priceProgram, {
+			accounts: {
+				authority,
+				owner: payer.publicKey,
+			}
+		});
+
+		let authorityData = await program.account.authority.fetch(authority);
+		assert.isTrue(authorityData.trustedPricePrograms.some(p => p.equals(priceProgram)));
+
+		await program.rpc.removePriceProgram(priceProgram, {
+			accounts: {
+				authority,
+				owner: payer.publicKey,
+			}
+		});
+
+		authorityData = await program.account.authority.fetch(authority);
+		assert.isFalse(authorityData.trustedPricePrograms.some(p => p.equals(priceProgram)));
+
+	});
+
+	it('only authority can trust price programs', async () => {
+
+		let user = await createUser(1);
+		let call = async () => {
+			await program.rpc.addPriceProgram(Keypair.generate().publicKey, {
+				accounts: {
+					authority,
+					owner: user.publicKey,
+				},
+				signers: [user]
+			});
+		};
+
+		await assertError(call);
+
+	});
+
+	it('should create and configure a vault to list', async () => {
+		[
+			listedVault,
+			listedPricingAccount,
+			listedFractionTreasury,
+			listedRedeemTreasury,
+			listedFractionMint,
+			listedLockedMint,
+		] = await vaultProgram.createVault(provider, authority);
+	});
+
+	it('list a vault without bidding', async () => {
+		let auctionBump;
+		[listing, auctionBump] = await findAuctionAccount(program, settings, listedVault);
+		let treasuryKeypair = Keypair.generate();
+		listingTreasury = treasuryKeypair.publicKey;
+
+		await program.rpc.list(auctionBump, {
+			accounts: {
+				authority,
+				auction: listing,
+				settings,
+				paymentTreasury: listingTreasury,
+				vault: listedVault,
+				externalPricingAccount: listedPricingAccount,
+				paymentMint: NATIVE_MINT,
+				payer: payer.publicKey,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [treasuryKeypair]
+		});
+
+		let auctionAccount = await program.account.auction.fetch(listing);
+		assert.isFalse(auctionAccount.combined);
+		assert.equal(auctionAccount.topBid.toNumber(), 0);
+		assert.deepEqual(auctionAccount.lister, payer.publicKey);
+	});
+
+	it('cannot close a listing before it expires', async () => {
+		let call = async () => await program.rpc.closeListing({
+			accounts: {
+				auction: listing,
+				paymentTreasury: listingTreasury,
+				lister: payer.publicKey,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			}
+		});
+		await assertError(call, "The auction has not yet finished");
+	});
+
+	it('the first bid on a listing has to use its payment treasury', async () => {
+		let auctionBump = (await findAuctionAccount(program, settings, listedVault))[1];
+		let [bid, bidBump] = await findBidAccount(program, listing, payer.publicKey);
+		let [fractionEscrow, escrowBump] = await findProgramAccount(program, ["escrow", bid]);
+		let bidTokenAccountKeypair = Keypair.generate();
+		let treasuryKeypair = Keypair.generate();
+		let fractionMintToken = new Token(connection, listedFractionMint, TOKEN_PROGRAM_ID, payer);
+		let outstandingFractionsTokenAccount = await fractionMintToken.createAccount(payer.publicKey);
+
+		let call = async () => await program.rpc.start(auctionBump, bidBump, escrowBump, new anchor.BN(1e9), new anchor.BN(0), null, {
+			accounts: {
+				authority,
+				auction: listing,
+				settings,
+				paymentTreasury: treasuryKeypair.publicKey,
+				bid,
+				bidTokenAccount: bidTokenAccountKeypair.publicKey,
+				fractionEscrow,
+				payingTokenAccount: payer.publicKey,
+				payingFractionAccount: outstandingFractionsTokenAccount,
+				payer: payer.publicKey,
+				fundingAuthority: payer.publicKey,
+				beneficiary: payer.publicKey,
+				fractionMint: listedFractionMint,
+				paymentMint: NATIVE_MINT,
+				vault: listedVault,
+				externalPricingAccount: listedPricingAccount,
+				outstandingFractionsTokenAccount,
+				vaultFractionTreasury: listedFractionTreasury,
+				vaultRedeemTreasury: listedRedeemTreasury,
+				vaultPda: (await findVaultPDA(listedVault))[0],
+				vaultProgram: VAULT_PROGRAM_ID,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [bidTokenAccountKeypair, treasuryKeypair]
+		});
+		await assertError(call, "The settings and payment treasury are not those of the listing");
+	});
+
+	it('first bid on a listing combines the vault', async () => {
+		let auctionBump = (await findAuctionAccount(program, settings, listedVault))[1];
+		let [bid, bidBump] = await findBidAccount(program, listing, payer.publicKey);
+		let [fractionEscrow, escrowBump] = await findProgramAccount(program, ["escrow", bid]);
+		let bidTokenAccountKeypair = Keypair.generate();
+		let fractionMintToken = new Token(connection, listedFractionMint, TOKEN_PROGRAM_ID, payer);
+		let outstandingFractionsTokenAccount = await fractionMintToken.createAccount(payer.publicKey);
+		let listingData = await program.account.auction.fetch(listing);
+
+		// Starting an auction on a listing which nobody has bid on takes it over
+		await program.rpc.start(auctionBump, bidBump, escrowBump, new anchor.BN(1e9), new anchor.BN(0), null, {
+			accounts: {
+				authority,
+				auction: listing,
+				settings,
+				paymentTreasury: listingTreasury,
+				bid,
+				bidTokenAccount: bidTokenAccountKeypair.publicKey,
+				fractionEscrow,
+				payingTokenAccount: payer.publicKey,
+				payingFractionAccount: outstandingFractionsTokenAccount,
+				payer: payer.publicKey,
+				fundingAuthority: payer.publicKey,
+				beneficiary: payer.publicKey,
+				fractionMint: listedFractionMint,
+				paymentMint: NATIVE_MINT,
+				vault: listedVault,
+				externalPricingAccount: listedPricingAccount,
+				outstandingFractionsTokenAccount,
+				vaultFractionTreasury: listedFractionTreasury,
+				vaultRedeemTreasury: listedRedeemTreasury,
+				vaultPda: (await findVaultPDA(listedVault))[0],
+				vaultProgram: VAULT_PROGRAM_ID,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [bidTokenAccountKeypair]
+		});
+
+		let auctionAccount = await program.account.auction.fetch(listing);
+		assert.isTrue(auctionAccount.combined);
+		assert.equal(auctionAccount.topBid.toNumber(), 1e9);
+		assert.deepEqual(auctionAccount.topBidder, payer.publicKey);
+		assert.isTrue(auctionAccount.endTimestamp.eq(listingData.endTimestamp));
+	});
+
+	const makeOffer = async (_offered, _bidder, _amount, _expiry) => {
+		let [newOffer, offerBump] = await findProgramAccount(program, ["offer", _offered.vault, _bidder.publicKey]);
+		let [newOfferAuthority, authorityBump] = await findProgramAccount(program, ["offer_authority", newOffer]);
+		let escrowKeypair = Keypair.generate();
+		let fractionAccountKeypair = Keypair.generate();
+
+		await program.rpc.makeOffer(offerBump, authorityBump, new anchor.BN(_amount), new anchor.BN(_expiry), {
+			accounts: {
+				authority,
+				offer: newOffer,
+				offerAuthority: newOfferAuthority,
+				escrow: escrowKeypair.publicKey,
+				fractionAccount: fractionAccountKeypair.publicKey,
+				settings,
+				vault: _offered.vault,
+				externalPricingAccount: _offered.priceAccount,
+				payingTokenAccount: _bidder.publicKey,
+				bidder: _bidder.publicKey,
+				fractionMint: _offered.fractionMint,
+				paymentMint: NATIVE_MINT,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [_bidder, escrowKeypair, fractionAccountKeypair]
+		});
+		return [newOffer, newOfferAuthority];
+	};
+
+	// The curator holds all of the fractions, and accepts offers with them
+	const acceptOffer = async (_offer, _offered, _votes) => {
+		let [offerVote, voteBump] = await findProgramAccount(program, ["offer_vote", _offer, payer.publicKey]);
+		await program.rpc.acceptOffer(voteBump, new anchor.BN(_votes), {
+			accounts: {
+				offer: _offer,
+				offerVote,
+				settings,
+				fractionAccount: (await program.account.offer.fetch(_offer)).fractionAccount,
+				voterFractionAccount: _offered.curatorFractionAccount,
+				voter: payer.publicKey,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			}
+		});
+		return offerVote;
+	};
+
+	const withdrawAcceptance = async (_offer, _offered, _votes) => {
+		let offerData = await program.account.offer.fetch(_offer);
+		await program.rpc.withdrawAcceptance(new anchor.BN(_votes), {
+			accounts: {
+				offer: _offer,
+				offerAuthority: (await findProgramAccount(program, ["offer_authority", _offer]))[0],
+				offerVote: (await findProgramAccount(program, ["offer_vote", _offer, payer.publicKey]))[0],
+				fractionAccount: offerData.fractionAccount,
+				voterFractionAccount: _offered.curatorFractionAccount,
+				voter: payer.publicKey,
+				bidder: offerData.bidder,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			}
+		});
+	};
+
+	const executeOffer = async () => {
+		let [auction, auctionBump] = await findAuctionAccount(program, settings, offered.vault);
+		let [bid, bidBump] = await findBidAccount(program, auction, offerBidder.publicKey);
+		let [fractionEscrow, escrowBump] = await findProgramAccount(program, ["escrow", bid]);
+		let offerData = await program.account.offer.fetch(offer);
+		let paymentTreasuryKeypair = Keypair.generate();
+		let bidTokenAccountKeypair = Keypair.generate();
+		let outstandingFractionsKeypair = Keypair.generate();
+
+		await program.rpc.executeOffer(auctionBump, bidBump, escrowBump, {
+			accounts: {
+				authority,
+				offer,
+				offerAuthority,
+				escrow: offerData.escrow,
+				fractionAccount: offerData.fractionAccount,
+				outstandingFractions: outstandingFractionsKeypair.publicKey,
+				auction,
+				settings,
+				paymentTreasury: paymentTreasuryKeypair.publicKey,
+				bid,
+				bidTokenAccount: bidTokenAccountKeypair.publicKey,
+				fractionEscrow,
+				bidder: offerBidder.publicKey,
+				payer: payer.publicKey,
+				fractionMint: offered.fractionMint,
+				paymentMint: NATIVE_MINT,
+				vault: offered.vault,
+				externalPricingAccount: offered.priceAccount,
+				priceProgram: PRICE_PROGRAM_ID,
+				vaultFractionTreasury: offered.fractionTreasury,
+				vaultRedeemTreasury: offered.redeemTreasury,
+				vaultPda: (await findVaultPDA(offered.vault))[0],
+				vaultProgram: VAULT_PROGRAM_ID,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [paymentTreasuryKeypair, bidTokenAccountKeypair, outstandingFractionsKeypair]
+		});
+		return auction;
+	};
+
+	it('make a standing offer on a fractionalized vault', async () => {
+		offered = await fractionalizeNft();
+		offerBidder = await createUser(3);
+		[offer, offerAuthority] = await makeOffer(offered, offerBidder, 1e9, Math.floor(Date.now() / 1000) + 600);
+
+		let offerData = await program.account.offer.fetch(offer);
+		assert.equal(await getTokenAccountBalance(connection, offerData.escrow), 1e9);
+		assert.equal(offerData.votes.toNumber(), 0);
+		assert.isFalse(offerData.accepted);
+	});
+
+	it('cannot execute an offer without a majority', async () => {
+		await assertError(executeOffer, "The offer has not been accepted by a majority of the fractions");
+	});
+
+	it('fraction holders accept an offer', async () => {
+		let offerVote = await acceptOffer(offer, offered, 600_000);
+
+		// The fractions are escrowed with the offer, outside of the price tracker
+		let offerData = await program.account.offer.fetch(offer);
+		let offerVoteData = await program.account.offerVote.fetch(offerVote);
+		assert.equal(offerVoteData.pendingVotes.toNumber(), 600_000);
+		assert.equal(await getTokenAccountBalance(connection, offered.curatorFractionAccount), 400_000);
+		assert.equal(await getTokenAccountBalance(connection, offerData.fractionAccount), 600_000);
+	});
+
+	it('acceptances only count once warmed up', async () => {
+		assert.equal((await program.account.offer.fetch(offer)).votes.toNumber(), 0);
+		await assertError(executeOffer, "The offer has not been accepted by a majority of the fractions");
+	});
+
+	it('accepting again counts the warmed up acceptances', async () => {
+		await new Promise(r => setTimeout(r, 2000));
+		let offerVote = await acceptOffer(offer, offered, 0);
+
+		let offerVoteData = await program.account.offerVote.fetch(offerVote);
+		assert.equal((await program.account.offer.fetch(offer)).votes.toNumber(), 600_000);
+		assert.equal(offerVoteData.votes.toNumber(), 600_000);
+		assert.equal(offerVoteData.pendingVotes.toNumber(), 0);
+	});
+
+	it('execute an accepted offer', async () => {
+		let escrow = (await program.account.offer.fetch(offer)).escrow;
+		let auction = await executeOffer();
+
+		// The offer is settled as an auction which has already ended
+		let auctionAccount = await program.account.auction.fetch(auction);
+		assert.isTrue(auctionAccount.combined);
+		assert.equal(auctionAccount.topBid.toNumber(), 1e9);
+		assert.deepEqual(auctionAccount.topBidder, offerBidder.publicKey);
+		assert.isTrue(auctionAccount.endTimestamp.eq(auctionAccount.startTimestamp));
+		assert.isTrue((await program.account.offer.fetch(offer)).accepted);
+		assert.isNull(await connection.getAccountInfo(escrow));
+
+		// The vault was priced at the offer, leaving the accepted fractions to be redeemed
+		let offerData = await program.account.offer.fetch(offer);
+		assert.equal(await getTokenAccountBalance(connection, offerData.fractionAccount), 600_000);
+	});
+
+	it('withdraw an acceptance once the offer is executed', async () => {
+		let offerVote = (await findProgramAccount(program, ["offer_vote", offer, payer.publicKey]))[0];
+		await withdrawAcceptance(offer, offered, 600_000);
+
+		// The fractions are back with the holder, ready to be redeemed, and the emptied vote record is closed
+		assert.equal((await program.account.offer.fetch(offer)).votes.toNumber(), 0);
+		assert.equal(await getTokenAccountBalance(connection, offered.curatorFractionAccount), 1_000_000);
+		assert.isNull(await connection.getAccountInfo(offerVote));
+	});
+
+	it('refunding an offer closes it with the last withdrawal of its acceptances', async () => {
+		let refunded = await fractionalizeNft();
+		let refundedBidder = await createUser(2);
+		let [refundedOffer, refundedAuthority] = await makeOffer(refunded, refundedBidder, 1e9, Math.floor(Date.now() / 1000) + 2);
+		await acceptOffer(refundedOffer, refunded, 100_000);
+		let offerData = await program.account.offer.fetch(refundedOffer);
+
+		await new Promise(r => setTimeout(r, 3000));
+		let balance = await connection.getBalance(refundedBidder.publicKey);
+		await program.rpc.refundOffer({
+			accounts: {
+				offer: refundedOffer,
+				offerAuthority: refundedAuthority,
+				escrow: offerData.escrow,
+				fractionAccount: offerData.fractionAccount,
+				toAccount: refundedBidder.publicKey,
+				bidder: refundedBidder.publicKey,
+				paymentMint: NATIVE_MINT,
+				temporaryTokenAccount: (await findTemporaryTokenAccount(program, refundedBidder.publicKey))[0],
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [refundedBidder]
+		});
+		assert.isAbove(await connection.getBalance(refundedBidder.publicKey), balance + 1e9 - 1e7);
+		assert.isTrue((await program.account.offer.fetch(refundedOffer)).refunded);
+
+		await withdrawAcceptance(refundedOffer, refunded, 100_000);
+		assert.isNull(await connection.getAccountInfo(refundedOffer));
+		assert.isNull(await connection.getAccountInfo(offerData.fractionAccount));
+	});
+
 	it('start auction', async () => {
 		await start(1, 1, payer);
 	});
@@ -422,10 +1284,21 @@ describe('auction', () => {
 		var userA = payer;
 		var userB;
 		var userC;
+		var userD;
+		var userE;
+		var userF;
+		var userG;
+		var userH;
+		var pool;
 
 		before(async () => {
 			userB = await createUser(5);
 			userC = await createUser(5);
+			userD = await createUser(5);
+			userE = await createUser(5);
+			userF = await createUser(5);
+			userG = await createUser(5);
+			userH = await createUser(5);
 		});
 
 		it('cannot place bid smaller than current top bid', async () => {
@@ -439,37 +1312,119 @@ describe('auction', () => {
 		});
 
 		it('place bids', async () => {
-			await bid(userB, 1.1);
-			await bid(userC, 1.3);
+			// Not token accounts, so the refunds fall back to the bid token accounts
+			await bid(userB, 1.1, false, Keypair.generate().publicKey);
+			await bid(userC, 1.3, false, Keypair.generate().publicKey);
+		});
+
+		it('the refund account registered by the top bidder has to be passed', async () => {
+			let call = async () => await bid(userD, 1.4, false, null, userD, 0, Keypair.generate().publicKey);
+			await assertError(call, "The refund account is not the one registered with the bid");
+		});
+
+		it('place pooled bids', async () => {
+			pool = await createPool();
+			await contribute(pool, userG, 0.8);
+			await contribute(pool, userH, 0.7);
+			await poolBid(pool, 1.4);
+		});
+
+		it('contributions cannot be withdrawn while the pool holds the top bid', async () => {
+			let call = async () => await withdrawContribution(pool, userG);
+			await assertError(call, "Contributions cannot be withdrawn while the pool holds the top bid");
+		});
+
+		it('place bids with escrowed fractions', async () => {
+			let fractions = await getTokenAccountBalance(connection, destAccount);
+			// Credited against the fractions in circulation, leaving out the vault's fraction treasury
+			let supply = parseInt((await connection.getTokenSupply(fractionMint)).value.amount)
+				- await getTokenAccountBalance(connection, vaultFractionTreasury);
+			let escrowed = Math.floor(fractions / 2);
+			await bid(userA, 1.5, false, null, userA, escrowed);
+
+			let auction = (await findAuctionAccount(program, settings, vault))[0];
+			let bidAccount = await program.account.bid.fetch((await findBidAccount(program, auction, userA.publicKey))[0]);
+			assert.equal(bidAccount.fractionCredit.toNumber(), Math.floor(1.5e9 * escrowed / supply));
+			assert.equal(await getTokenAccountBalance(connection, destAccount), fractions - escrowed);
+		});
+
+		it('an outbid pool gets its refund back and can bid again', async () => {
+			let poolAccount = await program.account.pool.fetch(pool);
+			assert.equal(await getTokenAccountBalance(connection, poolAccount.tokenAccount), poolAccount.totalContributions.toNumber());
+
+			await contribute(pool, userF, 0.2);
+			await poolBid(pool, 1.6);
+		});
+
+		it('place bids in native SOL', async () => {
+			let fractions = await getTokenAccountBalance(connection, destAccount);
+			let refundAccount = await createNativeTokenAccount(provider, 0, userD);
+			await bid(userD, 1.7, true, refundAccount);
+			// Funded by another wallet on behalf of userE, who does not need to sign
+			await bid(userE, 1.8, true, null, userF);
+
+			// The outbid escrowed fractions have been returned
+			let auction = (await findAuctionAccount(program, settings, vault))[0];
+			let bidAccount = (await findBidAccount(program, auction, userA.publicKey))[0];
+			let fractionEscrow = (await findProgramAccount(program, ["escrow", bidAccount]))[0];
+			assert.isAbove(await getTokenAccountBalance(connection, destAccount), fractions);
+			assert.isNull(await connection.getAccountInfo(fractionEscrow));
+		});
+
+		it('refunded bids are closed', async () => {
+			let auction = (await findAuctionAccount(program, settings, vault))[0];
+			for (let bidder of [userA.publicKey, pool, userD.publicKey]) {
+				let bidAccount = (await findBidAccount(program, auction, bidder))[0];
+				assert.isNull(await connection.getAccountInfo(bidAccount));
+			}
+		});
+
+		it('contributors withdraw pro-rata from an outbid pool', async () => {
+			await withdrawContribution(pool, userG);
+			await withdrawContribution(pool, userH, true);
+			await withdrawContribution(pool, userF);
+			let poolAccount = await program.account.pool.fetch(pool);
+			assert.equal(poolAccount.totalContributions.toNumber(), 0);
+			assert.equal(await getTokenAccountBalance(connection, poolAccount.tokenAccount), 0);
 		});
 
 		it('same address cannot place multiple bids at once', async () => {
-			let call = async () => await bid(userC, 1.5);
+			let call = async () => await bid(userC, 1.9);
 			await assertError(call);
 		});
 
 		it('top bid cannot be withdrawn', async () => {
-			let call = async () => await withdrawBid(userC);
+			let call = async () => await withdrawBid(userE);
 			await assertError(call, "Top bid cannot be withdrawn");
 		});
 
 		it('withdraw bids', async () => {
-			await withdrawBid(userA);
-			await withdrawBid(userB);
+			await withdrawBid(userC);
+		});
+
+		it('top bid cannot be withdrawn in one go', async () => {
+			let call = async () => await withdrawBids(userE);
+			await assertError(call, "Top bid cannot be withdrawn");
+		});
+
+		it('withdraw bids in one go', async () => {
+			await withdrawBids(userB);
 		});
 
 		it('wait for auction to finish', async () => {
-			await new Promise(r => setTimeout(r, 5000));
+			await new Promise(r => setTimeout(r, 20000));
 		});
 
-		it('only winner can claim auctioned asset', async () => {
-			let call = async () => await claim(userA);
-			await assertError(call, "Only the auction winner can claim the winnings");
+		it('cannot claim before the auction is officially ended', async () => {
+			let call = async () => await claim(userE);
+			await assertError(call, "Fee has not yet been delivered");
 		});
 
 		it('officially end (for fees)', async () => {
 			let feeTokenAccount = await createNativeTokenAccount(provider, 0, payer);
 			let auction = (await findAuctionAccount(program, settings, vault))[0];
+			let [topBid] = await topBidInfo(auction);
+			let topBidder = (await program.account.bid.fetch(topBid)).bidder;
 			await program.rpc.end({
 				accounts : {
 					authority,
@@ -477,14 +1432,84 @@ describe('auction', () => {
 					vault,
 					settings,
 					paymentTreasury,
+					topBid,
+					fractionEscrow: (await findProgramAccount(program, ["escrow", topBid]))[0],
+					topBidder,
+					fractionMint,
+					redeemTreasury: vaultRedeemTreasury,
+					vaultPda: (await findVaultPDA(vault))[0],
+					vaultProgram: VAULT_PROGRAM_ID,
 					feeTokenAccount,
-					tokenProgram: TOKEN_PROGRAM_ID
+					paymentMint: NATIVE_MINT,
+					payer: payer.publicKey,
+					temporaryTokenAccount: (await findTemporaryTokenAccount(program, payer.publicKey))[0],
+					tokenProgram: TOKEN_PROGRAM_ID,
+					systemProgram: SystemProgram.programId,
+					rent: SYSVAR_RENT_PUBKEY,
 				}
 			});
 		})
 
+		it('only winner can claim auctioned asset', async () => {
+			let call = async () => await claim(userA);
+			await assertError(call, "Only the auction winner can claim the winnings");
+		});
+
 		it('claim auctioned asset', async () => {
-			await claim(userC);
+			await claim(userE);
+		});
+
+		it('cannot claim the same safety deposit box twice', async () => {
+			let call = async () => await claim(userE);
+			await assertError(call, "The safety deposit box has already been claimed");
+		});
+
+		it('cannot claim a box from another vault along with the rest', async () => {
+			let safetyDepositBox = (await findSafetyBox(vault, lockedMints[1]))[0];
+			let otherSafetyDepositBox = (await findSafetyBox(listedVault, listedLockedMint))[0];
+			let call = async () => await claimAll(userE, [safetyDepositBox, otherSafetyDepositBox]);
+			await assertError(call, "The safety deposit box does not belong to the auctioned vault");
+		});
+
+		it('cannot claim a box again along with the rest', async () => {
+			let safetyDepositBoxes = await Promise.all(lockedMints.map(async mint => (await findSafetyBox(vault, mint))[0]));
+			let call = async () => await claimAll(userE, safetyDepositBoxes);
+			await assertError(call, "The safety deposit box has already been claimed");
+		});
+
+		it('only winner can claim every box', async () => {
+			let safetyDepositBoxes = await Promise.all(lockedMints.slice(1).map(async mint => (await findSafetyBox(vault, mint))[0]));
+			let call = async () => await claimAll(userA, safetyDepositBoxes);
+			await assertError(call, "Only the auction winner can claim the winnings");
+		});
+
+		it('boxes are only claimed from the token vault program', async () => {
+			let safetyDepositBoxes = await Promise.all(lockedMints.slice(1).map(async mint => (await findSafetyBox(vault, mint))[0]));
+			let call = async () => await claimAll(userE, safetyDepositBoxes, { vaultProgram: PRICE_PROGRAM_ID });
+			await assertError(call, "The given program is not the token vault program");
+		});
+
+		it('boxes are only claimed through the PDA of the vault', async () => {
+			let safetyDepositBoxes = await Promise.all(lockedMints.slice(1).map(async mint => (await findSafetyBox(vault, mint))[0]));
+			let call = async () => await claimAll(userE, safetyDepositBoxes, { vaultPda: (await findVaultPDA(listedVault))[0] });
+			await assertError(call, "The given vault PDA is not derived from the vault");
+		});
+
+		it('the vault has to be a token vault', async () => {
+			let safetyDepositBoxes = await Promise.all(lockedMints.slice(1).map(async mint => (await findSafetyBox(vault, mint))[0]));
+			let call = async () => await claimAll(userE, safetyDepositBoxes, { vault: safetyDepositBoxes[0] });
+			await assertError(call, "The account is not an initialised token vault");
+		});
+
+		it('claim every box left in one go', async () => {
+			let safetyDepositBoxes = await Promise.all(lockedMints.slice(1).map(async mint => (await findSafetyBox(vault, mint))[0]));
+			await claimAll(userE, safetyDepositBoxes);
+		});
+
+		it('cannot claim every box twice', async () => {
+			let safetyDepositBoxes = await Promise.all(lockedMints.slice(1).map(async mint => (await findSafetyBox(vault, mint))[0]));
+			let call = async () => await claimAll(userE, safetyDepositBoxes);
+			await assertError(call, "The safety deposit box has already been claimed");
 		});
 
 		it('should redeem', async () => {
@@ -52,7 +52,7 @@ const createNativeTokenAccount = async (provider, amount, authority, payer) => {
     return newAccount.publicKey;
 }
 
-const createVault = async (provider, auctionAuthority) => {
+const createVault = async (provider, auctionAuthority, boxes = 1) => {
 
     const payer = provider.wallet.payer;
     const connection = provider.connection;
@@ -75,7 +75,7 @@ const createVault = async (provider, auctionAuthority) => {
     // Create the mints
     const redeemMint = splToken.NATIVE_MINT
     const fractionMint = await utils.createMint(connection, payer, vaultPDA);
-    const lockedMint = await utils.createMint(connection, payer, payer.publicKey);
+    const receiptMint = await utils.createMint(connection, payer, pricePDA);
 
     // Create the treasuries
     const redeemTreasury = await createNativeTokenAccount(provider, 0, vaultPDA, payer);  
@@ -83,12 +83,13 @@ const createVault = async (provider, auctionAuthority) => {
 
     // Init the price account
     const storeTokenAccount = web3.Keypair.generate();
-    await priceProgram.rpc.initialize(pricePDABump, new BN('1'), { 
+    await priceProgram.rpc.initialize(pricePDABump, new BN('1'), { quorum: new BN(5_000), minBand: new BN(2_000), maxBand: new BN(50_000), priceMode: { mean: {} }, warmUpPeriod: new BN(0), cooldownPeriod: new BN(0) }, { 
         accounts: {
             priceAccount: priceAccount.publicKey,
             storeMint: fractionMint.publicKey,
             redeemMint: redeemMint,
-            lockedMint: lockedMint.publicKey,
+            lockedMint: receiptMint.publicKey,
+            vault: vaultAccount.publicKey,
             store: storeTokenAccount.publicKey,
             storeAuthorityPda: pricePDA,
             signer: payer.publicKey,
@@ -96,6 +97,7 @@ const createVault = async (provider, auctionAuthority) => {
             systemProgram: web3.SystemProgram.programId,
             rent: web3.SYSVAR_RENT_PUBKEY,
         },
+        remainingAccounts: [{ pubkey: fractionTreasury, isWritable: false, isSigner: false }],
         signers: [storeTokenAccount, priceAccount]
     });
 
@@ -106,21 +108,31 @@ const createVault = async (provider, auctionAuthority) => {
         []
     );
 
-    // Another PDA
-    const [safetyBoxPDA, safetyBoxPDABump] = await utils.findSafetyBoxPDA(instruction.VAULT_PROGRAM_ID, vaultAccount, lockedMint);
+    // Lock a token of a new mint in each safety deposit box
+    const lockedMints = [];
+    const storeAccounts = [];
+    for (let i = 0; i < boxes; i++) {
+        const lockedMint = await utils.createMint(connection, payer, payer.publicKey);
 
-    const storeFromAccount = await lockedMint.createAccount(payer.publicKey);
-    const storeAccount = await lockedMint.createAccount(vaultPDA);
+        // Another PDA
+        const [safetyBoxPDA, safetyBoxPDABump] = await utils.findSafetyBoxPDA(instruction.VAULT_PROGRAM_ID, vaultAccount, lockedMint);
 
-    // Put some tokens in the token account
-    await lockedMint.mintTo(storeFromAccount, payer.publicKey, [], 1);
+        const storeFromAccount = await lockedMint.createAccount(payer.publicKey);
+        const storeAccount = await lockedMint.createAccount(vaultPDA);
 
-    await provider.send(
-        new web3.Transaction().add(
-            instruction.addTokenToInactiveVaultInstruction(safetyBoxPDA, storeFromAccount, storeAccount, vaultAccount, payer, payer, payer, new BN('1')) 
-        ),
-        []
-    )
+        // Put some tokens in the token account
+        await lockedMint.mintTo(storeFromAccount, payer.publicKey, [], 1);
+
+        await provider.send(
+            new web3.Transaction().add(
+                instruction.addTokenToInactiveVaultInstruction(safetyBoxPDA, storeFromAccount, storeAccount, vaultAccount, payer, payer, payer, new BN('1')) 
+            ),
+            []
+        )
+
+        lockedMints.push(lockedMint.publicKey);
+        storeAccounts.push(storeAccount);
+    }
 
     await provider.send(
         new web3.Transaction().add(
@@ -137,20 +149,25 @@ const createVault = async (provider, auctionAuthority) => {
         []
     );
     
+    const receiptAccount = await receiptMint.createAccount(payer.publicKey);
     const [userPDA, userPDABump] = await findUserPricePDA(payer.publicKey, priceAccount.publicKey, priceProgram.programId);
     await priceProgram.rpc.addVote(pricePDABump, userPDABump, new BN(510_000), new BN(1e9), {
         accounts: {
             userPda: userPDA,
             priceAccount: priceAccount.publicKey,
+            vault: vaultAccount.publicKey,
             storeMint: fractionMint.publicKey,
             userTokenAccount: destAccount,
+            receiptAccount,
             store: storeTokenAccount.publicKey,
             storeAuthorityPda: pricePDA,
+            lockedMint: receiptMint.publicKey,
             signer: payer.publicKey,
             tokenProgram: splToken.TOKEN_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
             rent: web3.SYSVAR_RENT_PUBKEY,
-        }
+        },
+        remainingAccounts: [{ pubkey: fractionTreasury, isWritable: false, isSigner: false }]
     })
 
     provider.send(
@@ -164,9 +181,10 @@ const createVault = async (provider, auctionAuthority) => {
         fractionTreasury,
         redeemTreasury,
         fractionMint.publicKey,
-        lockedMint.publicKey,
-        storeAccount,
-        destAccount
+        lockedMints[0],
+        storeAccounts[0],
+        destAccount,
+        lockedMints
     ]
 
 }
@@ -42,6 +42,11 @@ const priceTrackerIDL = {
             "isMut": false,
             "isSigner": false
           },
+          {
+            "name": "vault",
+            "isMut": false,
+            "isSigner": false
+          },
           {
             "name": "signer",
             "isMut": true,
@@ -71,6 +76,32 @@ const priceTrackerIDL = {
           {
             "name": "minPrice",
             "type": "u64"
+          },
+          {
+            "name": "quorum",
+            "type": "u64"
+          },
+          {
+            "name": "minBand",
+            "type": "u64"
+          },
+          {
+            "name": "maxBand",
+            "type": "u64"
+          },
+          {
+            "name": "priceMode",
+            "type": {
+              "defined": "PriceMode"
+            }
+          },
+          {
+            "name": "warmUpPeriod",
+            "type": "u64"
+          },
+          {
+            "name": "cooldownPeriod",
+            "type": "u64"
           }
         ]
       },
@@ -153,6 +184,11 @@ const priceTrackerIDL = {
             "isMut": true,
             "isSigner": false
           },
+          {
+            "name": "vault",
+            "isMut": false,
+            "isSigner": false
+          },
           {
             "name": "store",
             "isMut": true,
@@ -163,6 +199,11 @@ const priceTrackerIDL = {
             "isMut": true,
             "isSigner": false
           },
+          {
+            "name": "receiptAccount",
+            "isMut": true,
+            "isSigner": false
+          },
           {
             "name": "userPda",
             "isMut": true,
@@ -178,6 +219,11 @@ const priceTrackerIDL = {
             "isMut": false,
             "isSigner": false
           },
+          {
+            "name": "lockedMint",
+            "isMut": true,
+            "isSigner": false
+          },
           {
             "name": "signer",
             "isMut": false,
@@ -226,6 +272,11 @@ const priceTrackerIDL = {
             "isMut": true,
             "isSigner": false
           },
+          {
+            "name": "vault",
+            "isMut": false,
+            "isSigner": false
+          },
           {
             "name": "store",
             "isMut": true,
@@ -236,6 +287,16 @@ const priceTrackerIDL = {
             "isMut": true,
             "isSigner": false
           },
+          {
+            "name": "receiptAccount",
+            "isMut": true,
+            "isSigner": false
+          },
+          {
+            "name": "voter",
+            "isMut": false,
+            "isSigner": false
+          },
           {
             "name": "userPda",
             "isMut": true,
@@ -251,6 +312,11 @@ const priceTrackerIDL = {
             "isMut": false,
             "isSigner": false
           },
+          {
+            "name": "lockedMint",
+            "isMut": true,
+            "isSigner": false
+          },
           {
             "name": "signer",
             "isMut": false,
@@ -286,7 +352,7 @@ const priceTrackerIDL = {
           "fields": [
             {
               "name": "reserveTotal",
-              "type": "u64"
+              "type": "u128"
             },
             {
               "name": "totalVotes",
@@ -311,6 +377,62 @@ const priceTrackerIDL = {
             {
               "name": "authority",
               "type": "publicKey"
+            },
+            {
+              "name": "vault",
+              "type": "publicKey"
+            },
+            {
+              "name": "finalized",
+              "type": "bool"
+            },
+            {
+              "name": "quorum",
+              "type": "u64"
+            },
+            {
+              "name": "minBand",
+              "type": "u64"
+            },
+            {
+              "name": "maxBand",
+              "type": "u64"
+            },
+            {
+              "name": "excludedAccounts",
+              "type": {
+                "vec": "publicKey"
+              }
+            },
+            {
+              "name": "priceScale",
+              "type": "u128"
+            },
+            {
+              "name": "fractionScale",
+              "type": "u128"
+            },
+            {
+              "name": "priceMode",
+              "type": {
+                "defined": "PriceMode"
+              }
+            },
+            {
+              "name": "priceBuckets",
+              "type": "publicKey"
+            },
+            {
+              "name": "medianPrice",
+              "type": "u64"
+            },
+            {
+              "name": "warmUpPeriod",
+              "type": "u64"
+            },
+            {
+              "name": "cooldownPeriod",
+              "type": "u64"
             }
           ]
         }
@@ -327,6 +449,28 @@ const priceTrackerIDL = {
             {
               "name": "votes",
               "type": "u64"
+            },
+            {
+              "name": "pendingPrice",
+              "type": "u64"
+            },
+            {
+              "name": "pendingVotes",
+              "type": "u64"
+            },
+            {
+              "name": "lastVoteTimestamp",
+              "type": "u64"
+            },
+            {
+              "name": "delegatedVotes",
+              "type": "u64"
+            },
+            {
+              "name": "delegate",
+              "type": {
+                "option": "publicKey"
+              }
             }
           ]
         }
@@ -356,6 +500,22 @@ const priceTrackerIDL = {
         }
       }
     ],
+    "types": [
+      {
+        "name": "PriceMode",
+        "type": {
+          "kind": "enum",
+          "variants": [
+            {
+              "name": "Mean"
+            },
+            {
+              "name": "Median"
+            }
+          ]
+        }
+      }
+    ],
     "errors": [
       {
         "code": 300,
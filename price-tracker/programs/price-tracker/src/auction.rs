@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    self,
+    instruction::{AccountMeta, Instruction},
+    system_program,
+    sysvar
+};
+
+// The auction has no deployments of its own on other clusters yet, so its address is the same on each.
+pub mod auction_program {
+    use anchor_lang::prelude::*;
+
+    declare_id!("4h3i7ER3q3pE5LSAedStJaYUcCKd3oXWBKPf3iUWsgGS");
+}
+
+// The auction program's authority, which signs for its vaults.
+pub fn find_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"authority".as_ref(), auction_program::ID.as_ref()], &auction_program::ID)
+}
+
+// Anchor instruction discriminator, the first 8 bytes of sha256("global:redeem").
+const REDEEM_DISCRIMINATOR: [u8; 8] = [184, 12, 86, 149, 70, 196, 97, 225];
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_redeem_instruction(
+    program_id: Pubkey,
+    auction: Pubkey,
+    settings: Pubkey,
+    vault: Pubkey,
+    payment_treasury: Pubkey,
+    redeem_treasury: Pubkey,
+    source_token_account: Pubkey,
+    destination_token_account: Pubkey,
+    fraction_mint: Pubkey,
+    payment_mint: Pubkey,
+    temporary_token_account: Pubkey,
+    vault_pda: Pubkey,
+    vault_program: Pubkey,
+    redeemer: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(auction, false),
+            AccountMeta::new_readonly(settings, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(payment_treasury, false),
+            AccountMeta::new(redeem_treasury, false),
+            AccountMeta::new(source_token_account, false),
+            AccountMeta::new(destination_token_account, false),
+            AccountMeta::new(fraction_mint, false),
+            AccountMeta::new_readonly(payment_mint, false),
+            AccountMeta::new(temporary_token_account, false),
+            AccountMeta::new_readonly(vault_pda, false),
+            AccountMeta::new_readonly(vault_program, false),
+            AccountMeta::new(redeemer, true),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: REDEEM_DISCRIMINATOR.to_vec(),
+    }
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    pub auction: AccountInfo<'info>,
+    pub settings: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub payment_treasury: AccountInfo<'info>,
+    pub redeem_treasury: AccountInfo<'info>,
+    pub source_token_account: AccountInfo<'info>,
+    pub destination_token_account: AccountInfo<'info>,
+    pub fraction_mint: AccountInfo<'info>,
+    pub payment_mint: AccountInfo<'info>,
+    pub temporary_token_account: AccountInfo<'info>,
+    pub vault_pda: AccountInfo<'info>,
+    pub vault_program: AccountInfo<'info>,
+    pub redeemer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>
+}
+
+pub fn redeem<'a, 'b, 'c, 'info>(ctx: CpiContext<'a, 'b, 'c, 'info, Redeem<'info>>) -> ProgramResult {
+    let ix = create_redeem_instruction(
+        auction_program::ID,
+        *ctx.accounts.auction.key,
+        *ctx.accounts.settings.key,
+        *ctx.accounts.vault.key,
+        *ctx.accounts.payment_treasury.key,
+        *ctx.accounts.redeem_treasury.key,
+        *ctx.accounts.source_token_account.key,
+        *ctx.accounts.destination_token_account.key,
+        *ctx.accounts.fraction_mint.key,
+        *ctx.accounts.payment_mint.key,
+        *ctx.accounts.temporary_token_account.key,
+        *ctx.accounts.vault_pda.key,
+        *ctx.accounts.vault_program.key,
+        *ctx.accounts.redeemer.key,
+    );
+    solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.auction.clone(),
+            ctx.accounts.settings.clone(),
+            ctx.accounts.vault.clone(),
+            ctx.accounts.payment_treasury.clone(),
+            ctx.accounts.redeem_treasury.clone(),
+            ctx.accounts.source_token_account.clone(),
+            ctx.accounts.destination_token_account.clone(),
+            ctx.accounts.fraction_mint.clone(),
+            ctx.accounts.payment_mint.clone(),
+            ctx.accounts.temporary_token_account.clone(),
+            ctx.accounts.vault_pda.clone(),
+            ctx.accounts.vault_program.clone(),
+            ctx.accounts.redeemer.clone(),
+            ctx.accounts.token_program.clone(),
+            ctx.accounts.system_program.clone(),
+            ctx.accounts.rent.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds
+    )
+}
\ No newline at end of file
@@ -13,16 +13,49 @@ pub enum ErrorCode {
     #[msg("Numerical Overflow Error")]
     NumericalOverflowError,
 
-    #[msg("The price must be less than 5x the current weighted average")]
+    #[msg("The price must be within the allowed band above the current weighted average")]
     PriceTooHigh,
 
     #[msg("The price must be greater than the minimum set price")]
     PriceBelowMinimum,
 
-    #[msg("The price must be greater than 1/5th the current weighted average")]
+    #[msg("The price must be within the allowed band below the current weighted average")]
     PriceTooLow,
 
     #[msg("Cannot remove zero votes")]
     AmountIsZero,
 
+    #[msg("The vault is not a token vault priced by this price account")]
+    InvalidVault,
+
+    #[msg("The vault has not been combined yet")]
+    VaultNotCombined,
+
+    #[msg("The given program is not the auction program")]
+    InvalidAuctionProgram,
+
+    #[msg("Votes can no longer be changed as the vault has been combined")]
+    VotesFinalized,
+
+    #[msg("The quorum must be more than zero and at most the whole supply")]
+    InvalidQuorum,
+
+    #[msg("The price band must include the current weighted average")]
+    InvalidPriceBand,
+
+    #[msg("The accounts excluded from the supply do not match those of the price account")]
+    InvalidExcludedAccounts,
+
+    #[msg("The price buckets are not those of the price account")]
+    InvalidPriceBuckets,
+
+    #[msg("Votes cannot be removed until the cooldown after the last vote has passed")]
+    VotesLocked,
+
+    #[msg("The votes are delegated, the delegation has to be revoked first")]
+    VotesDelegated,
+
+    #[msg("Only counted votes can be delegated, to another voter with votes of their own who has not delegated them")]
+    InvalidDelegation,
+
 }
\ No newline at end of file
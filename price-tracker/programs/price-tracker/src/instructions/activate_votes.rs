@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{self, ExternalPriceAccount, TokenInfo, UserInfo};
+use crate::errors::ErrorCode;
+use crate::vault;
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8, user_bump: u8)]
+pub struct ActivateVotes<'info> {
+
+    #[account(mut, owner = crate::id())]
+    pub price_account: Account<'info, ExternalPriceAccount>,
+
+    // vault must be the one being priced
+    #[account(constraint = vault.key() == store_authority_pda.vault)]
+    pub vault: AccountInfo<'info>,
+
+    // Anyone can count a voter's votes once they have warmed up
+    pub voter: AccountInfo<'info>,
+
+    #[account(
+        mut, 
+        seeds = [b"user".as_ref(), voter.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = user_bump,
+        owner = crate::id()
+    )]
+    pub user_pda: Account<'info, UserInfo>,
+
+    #[account(
+        mut, 
+        seeds = [b"price".as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = pda_bump,
+        owner = crate::id()
+    )]
+    pub store_authority_pda: Account<'info, TokenInfo>,
+
+    #[account(constraint = store_mint.key() == store_authority_pda.mint)]
+    pub store_mint: Account<'info, Mint>,
+
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ActivateVotes<'info>>) -> ProgramResult {
+
+    // Writable accounts
+    let token_info = &mut ctx.accounts.store_authority_pda;
+    let user_info = &mut ctx.accounts.user_pda;
+    let price_account = &mut ctx.accounts.price_account;
+
+    if token_info.finalized || vault::is_finalized(&ctx.accounts.vault, &price_account.key())? {
+        return Err(ErrorCode::VotesFinalized.into());
+    }
+
+    let (price_buckets, excluded_accounts) = token_info.split_price_buckets(ctx.remaining_accounts);
+    token_info.activate_votes(user_info, price_buckets, state::current_timestamp()?)?;
+
+    let circulating_supply = token_info.circulating_supply(&ctx.accounts.store_mint, excluded_accounts)?;
+    price_account.allowed_to_combine = token_info.has_quorum(circulating_supply)?;
+    price_account.price_per_share = token_info.price_per_share()?;
+    Ok(())
+}
\ No newline at end of file
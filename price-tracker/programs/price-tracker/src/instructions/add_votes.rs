@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
-use crate::state::{ExternalPriceAccount, TokenInfo, UserInfo};
+use anchor_spl::token::{self, Transfer, MintTo, Token, Mint, TokenAccount};
+use crate::state::{self, ExternalPriceAccount, TokenInfo, UserInfo, BPS_DENOMINATOR};
 use crate::errors::ErrorCode;
+use crate::vault;
 
 #[derive(Accounts)]
 #[instruction(pda_bump: u8, user_bump: u8)]
@@ -11,6 +12,10 @@ pub struct AddVotes<'info> {
     #[account(mut, owner = crate::id())]
     pub price_account: ProgramAccount<'info, ExternalPriceAccount>,
 
+    // vault must be the one being priced
+    #[account(constraint = vault.key() == store_authority_pda.vault)]
+    pub vault: AccountInfo<'info>,
+
     // authority must be pda
     // mint must be store_mint
     #[account(
@@ -31,8 +36,17 @@ pub struct AddVotes<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    // receives the receipts for the votes
+    // mint must be locked mint
     #[account(
-        init_if_needed, payer = signer, space = 8 + 16,
+        mut,
+        constraint = receipt_account.mint == store_authority_pda.locked_mint,
+        owner = token::ID
+    )]
+    pub receipt_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed, payer = signer, space = 8 + UserInfo::space(),
         seeds = [b"user".as_ref(), signer.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
         bump = user_bump,
         owner = crate::id()
@@ -49,6 +63,9 @@ pub struct AddVotes<'info> {
 
     pub store_mint: Account<'info, Mint>,
 
+    #[account(mut, constraint = locked_mint.key() == store_authority_pda.locked_mint)]
+    pub locked_mint: Account<'info, Mint>,
+
     pub signer: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
@@ -59,7 +76,7 @@ pub struct AddVotes<'info> {
 
 }
 
-pub fn handler(ctx: Context<AddVotes>, votes: u64, price: u64) -> ProgramResult {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AddVotes<'info>>, pda_bump: u8, votes: u64, price: u64) -> ProgramResult {
 
     // Writable accounts
     let token_info = &mut ctx.accounts.store_authority_pda;
@@ -70,7 +87,13 @@ pub fn handler(ctx: Context<AddVotes>, votes: u64, price: u64) -> ProgramResult
     let user_token_account = &ctx.accounts.user_token_account;
     let store_mint = &ctx.accounts.store_mint;
 
-    // 1. Make sure the user_token_account has enough tokens
+    // 1. Votes are frozen once the vault has been combined, and while they are delegated
+    if token_info.finalized || vault::is_finalized(&ctx.accounts.vault, &price_account.key())? {
+        return Err(ErrorCode::VotesFinalized.into());
+    }
+    user_info.assert_not_delegated()?;
+
+    // 2. Make sure the user_token_account has enough tokens
     if user_token_account.amount < votes {
         return Err(ErrorCode::NotEnoughTokensInAccount.into());
     }
@@ -79,27 +102,35 @@ pub fn handler(ctx: Context<AddVotes>, votes: u64, price: u64) -> ProgramResult
         return Err(ErrorCode::PriceBelowMinimum.into())
     }
 
+    // The band is taken around the unscaled current price, in the same terms as the vote
     if token_info.total_votes > 0 {
-        let min_price = match price_account.price_per_share.checked_div(5) {
-            Some(val) => val,
+        let current_price = token_info.current_price()?;
+        let min_price = match (current_price as u128).checked_mul(token_info.min_band as u128) {
+            Some(val) => match val.checked_div(BPS_DENOMINATOR as u128) {
+                Some(val) => val,
+                None => return Err(ErrorCode::NumericalOverflowError.into()),
+            },
             None => return Err(ErrorCode::NumericalOverflowError.into()),
         };
 
-        if price < min_price {
+        if (price as u128) < min_price {
             return Err(ErrorCode::PriceTooLow.into());
         }
     
-        let max_price = match price_account.price_per_share.checked_mul(5) {
-            Some(val) => val,
+        let max_price = match (current_price as u128).checked_mul(token_info.max_band as u128) {
+            Some(val) => match val.checked_div(BPS_DENOMINATOR as u128) {
+                Some(val) => val,
+                None => return Err(ErrorCode::NumericalOverflowError.into()),
+            },
             None => return Err(ErrorCode::NumericalOverflowError.into()),
         };
     
-        if price > max_price {
+        if (price as u128) > max_price {
             return Err(ErrorCode::PriceTooHigh.into());
         }
     }
     
-    // 2. Transfer tokens to the store
+    // 3. Transfer tokens to the store
     if (votes > 0) {
         token::transfer(
             CpiContext::new(
@@ -114,46 +145,39 @@ pub fn handler(ctx: Context<AddVotes>, votes: u64, price: u64) -> ProgramResult
         )?;
     }
 
-    token_info.reserve_total -= match (user_info.votes as u128).checked_mul(user_info.price as u128) {
-        Some(val) => match (val).checked_div(1e9 as u128) {
-            Some(val) => val as u64,
-            None => return Err(ErrorCode::NumericalOverflowError.into()),
-        },
-        None => return Err(ErrorCode::NumericalOverflowError.into()),
-    };
-
-    user_info.price = price;
-    user_info.votes += votes;
+    // 4. The votes only count once they have warmed up, when the user's earlier votes
+    //    move over to the new price along with them
+    let now = state::current_timestamp()?;
+    let (price_buckets, excluded_accounts) = token_info.split_price_buckets(ctx.remaining_accounts);
+    token_info.activate_votes(user_info, price_buckets, now)?;
 
-    token_info.reserve_total += match (user_info.votes as u128).checked_mul(user_info.price as u128) {
-        Some(val) => match (val).checked_div(1e9 as u128) {
-            Some(val) => val as u64,
-            None => return Err(ErrorCode::NumericalOverflowError.into()),
-        },
-        None => return Err(ErrorCode::NumericalOverflowError.into()),
-    };
-
-    token_info.total_votes += votes;
-    
-    let half_supply = match store_mint.supply.checked_div(2) {
+    user_info.pending_price = price;
+    user_info.pending_votes = match user_info.pending_votes.checked_add(votes) {
         Some(val) => val,
         None => return Err(ErrorCode::NumericalOverflowError.into()),
     };
-    
-    if token_info.total_votes > half_supply {
-        price_account.allowed_to_combine = true;
-    }
+    user_info.last_vote_timestamp = now;
 
-    if token_info.total_votes > 0 {
-        price_account.price_per_share = match (token_info.reserve_total as u128).checked_mul(1e9 as u128) {
-            Some(val) => match val.checked_div(token_info.total_votes as u128) {
-                Some(val) => std::cmp::max(token_info.min_price, val as u64),
-                None => return Err(ErrorCode::NumericalOverflowError.into()),
-            },
-            None => return Err(ErrorCode::NumericalOverflowError.into()),
-        };
-    } else {
-        price_account.price_per_share = token_info.min_price;
+    token_info.activate_votes(user_info, price_buckets, now)?;
+    
+    let circulating_supply = token_info.circulating_supply(store_mint, excluded_accounts)?;
+    price_account.allowed_to_combine = token_info.has_quorum(circulating_supply)?;
+    price_account.price_per_share = token_info.price_per_share()?;
+
+    // 5. Mint the voter a receipt for each staked token
+    if votes > 0 {
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                MintTo {
+                    mint: ctx.accounts.locked_mint.to_account_info(),
+                    to: ctx.accounts.receipt_account.to_account_info(),
+                    authority: ctx.accounts.store_authority_pda.to_account_info()
+                },
+                &[&[b"price".as_ref(), price_account.key().as_ref(), ctx.program_id.as_ref(), &[pda_bump]]]
+            ), 
+            votes
+        )?;
     }
 
     Ok(())
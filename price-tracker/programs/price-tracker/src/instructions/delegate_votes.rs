@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::state::{self, Delegation, ExternalPriceAccount, TokenInfo, UserInfo};
+use crate::errors::ErrorCode;
+use crate::vault;
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8, user_bump: u8, delegate_bump: u8, delegation_bump: u8)]
+pub struct DelegateVotes<'info> {
+
+    #[account(mut, owner = crate::id())]
+    pub price_account: Account<'info, ExternalPriceAccount>,
+
+    // vault must be the one being priced
+    #[account(constraint = vault.key() == store_authority_pda.vault)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut, 
+        seeds = [b"user".as_ref(), signer.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = user_bump,
+        owner = crate::id()
+    )]
+    pub user_pda: Account<'info, UserInfo>,
+
+    // delegate must be another voter
+    #[account(constraint = delegate.key() != signer.key())]
+    pub delegate: AccountInfo<'info>,
+
+    #[account(
+        mut, 
+        seeds = [b"user".as_ref(), delegate.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = delegate_bump,
+        owner = crate::id()
+    )]
+    pub delegate_pda: Account<'info, UserInfo>,
+
+    #[account(
+        init, payer = signer, space = 8 + Delegation::space(),
+        seeds = [b"delegation".as_ref(), signer.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = delegation_bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(
+        mut, 
+        seeds = [b"price".as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = pda_bump,
+        owner = crate::id()
+    )]
+    pub store_authority_pda: Account<'info, TokenInfo>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, DelegateVotes<'info>>) -> ProgramResult {
+
+    // Writable accounts
+    let token_info = &mut ctx.accounts.store_authority_pda;
+    let user_info = &mut ctx.accounts.user_pda;
+    let delegate_info = &mut ctx.accounts.delegate_pda;
+    let price_account = &mut ctx.accounts.price_account;
+
+    if token_info.finalized || vault::is_finalized(&ctx.accounts.vault, &price_account.key())? {
+        return Err(ErrorCode::VotesFinalized.into());
+    }
+
+    // 1. Only votes which count can be delegated, and only to a voter whose own votes
+    //    count and who is not a delegator themselves, so delegations never chain
+    let (price_buckets, _) = token_info.split_price_buckets(ctx.remaining_accounts);
+    token_info.activate_votes(user_info, price_buckets, state::current_timestamp()?)?;
+    if user_info.pending_votes > 0 || user_info.votes == 0 || user_info.delegated_votes > 0 {
+        return Err(ErrorCode::InvalidDelegation.into());
+    }
+    if delegate_info.votes == 0 || delegate_info.delegate.is_some() {
+        return Err(ErrorCode::InvalidDelegation.into());
+    }
+
+    // 2. The votes count at the delegate's price from now on, and move with it
+    let votes = user_info.votes;
+    token_info.move_votes(price_buckets, votes, user_info.price, delegate_info.price)?;
+    delegate_info.delegated_votes = match delegate_info.delegated_votes.checked_add(votes) {
+        Some(val) => val,
+        None => return Err(ErrorCode::NumericalOverflowError.into()),
+    };
+    user_info.delegate = Some(ctx.accounts.delegate.key());
+
+    let delegation = &mut ctx.accounts.delegation;
+    delegation.delegator = ctx.accounts.signer.key();
+    delegation.delegate = ctx.accounts.delegate.key();
+    delegation.votes = votes;
+
+    price_account.price_per_share = token_info.price_per_share()?;
+    Ok(())
+}
\ No newline at end of file
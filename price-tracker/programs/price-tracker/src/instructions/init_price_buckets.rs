@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ExternalPriceAccount, PriceBuckets, PriceMode, TokenInfo};
+
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8)]
+pub struct InitPriceBuckets<'info> {
+
+    #[account(owner = crate::id())]
+    pub price_account: Account<'info, ExternalPriceAccount>,
+
+    // only vaults priced at the median keep buckets, and only the first ones given
+    #[account(
+        mut,
+        seeds = [b"price".as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = pda_bump,
+        constraint = store_authority_pda.price_mode == PriceMode::Median,
+        constraint = store_authority_pda.price_buckets == Pubkey::default()
+    )]
+    pub store_authority_pda: Account<'info, TokenInfo>,
+
+    // The buckets are too large to be created by the program, so the signer creates
+    // the account owned by the price tracker in the same transaction
+    #[account(
+        zero,
+        constraint = price_buckets.to_account_info().data_len() == 8 + PriceBuckets::space()
+    )]
+    pub price_buckets: AccountLoader<'info, PriceBuckets>,
+
+}
+
+pub fn handler(ctx: Context<InitPriceBuckets>) -> ProgramResult {
+    let mut buckets = ctx.accounts.price_buckets.load_init()?;
+    buckets.price_account = ctx.accounts.price_account.key();
+
+    let token_info = &mut ctx.accounts.store_authority_pda;
+    token_info.price_buckets = ctx.accounts.price_buckets.key();
+    Ok(())
+}
\ No newline at end of file
@@ -11,7 +11,7 @@ pub struct InitUserInfo<'info> {
     #[account(owner = crate::id())]
     pub price_account: Account<'info, ExternalPriceAccount>,
 
-    #[account(init, payer = signer, space = 8 + 16, seeds = [b"user".as_ref(), signer.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], bump = user_bump)]
+    #[account(init, payer = signer, space = 8 + UserInfo::space(), seeds = [b"user".as_ref(), signer.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], bump = user_bump)]
     pub user_pda: Account<'info, UserInfo>,
 
     pub store_mint: Account<'info, Mint>,
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{Token, Mint, TokenAccount};
-use crate::state::{ExternalPriceAccount, TokenInfo};
+use crate::state::{self, ExternalPriceAccount, TokenInfo, VoteSettings, EXTERNAL_ACCOUNT_KEY_V1, MAX_EXCLUDED_ACCOUNTS};
+use crate::errors::ErrorCode;
+use crate::vault;
 
 #[derive(Accounts)]
 #[instruction(pda_bump: u8)]
@@ -15,7 +18,7 @@ pub struct Initialize<'info> {
     #[account(
         init, 
         payer = signer, 
-        space = 8 + 24 + 32 + 32 + 32 + 32, 
+        space = 8 + TokenInfo::space(), 
         seeds = [b"price".as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
         bump = pda_bump
     )]
@@ -25,8 +28,17 @@ pub struct Initialize<'info> {
 
     pub redeem_mint: Account<'info, Mint>,
 
+    // Receipts for staked votes, minted by the store authority
+    #[account(
+        constraint = locked_mint.mint_authority == COption::Some(store_authority_pda.key()),
+        constraint = locked_mint.supply == 0
+    )]
     pub locked_mint: Account<'info, Mint>,
 
+    // Not created until after the price account, so it is only recorded here
+    // and checked against the price account once it exists.
+    pub vault: AccountInfo<'info>,
+
     #[account(mut)]
     pub signer: Signer<'info>,
 
@@ -38,7 +50,24 @@ pub struct Initialize<'info> {
 
 }
 
-pub fn handler(ctx: Context<Initialize>, min_price: u64) -> ProgramResult {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Initialize<'info>>,
+    min_price: u64,
+    settings: VoteSettings
+) -> ProgramResult {
+    TokenInfo::assert_valid_settings(settings.quorum, settings.min_band, settings.max_band)?;
+    vault::assert_not_created(&ctx.accounts.vault)?;
+
+    // Token accounts of the store mint which are left out of the quorum are passed as remaining accounts
+    if ctx.remaining_accounts.len() > MAX_EXCLUDED_ACCOUNTS {
+        return Err(ErrorCode::InvalidExcludedAccounts.into());
+    }
+    let mut excluded_accounts = Vec::new();
+    for info in ctx.remaining_accounts.iter() {
+        state::excluded_balance(&ctx.accounts.store_mint, info)?;
+        excluded_accounts.push(info.key());
+    }
+
     let price_account = &mut ctx.accounts.price_account;
     let token_info = &mut ctx.accounts.store_authority_pda;
     token_info.min_price = min_price;
@@ -46,6 +75,21 @@ pub fn handler(ctx: Context<Initialize>, min_price: u64) -> ProgramResult {
     token_info.mint = ctx.accounts.store_mint.key();
     token_info.locked_mint = ctx.accounts.locked_mint.key();
     token_info.authority = ctx.accounts.signer.key();
+    token_info.vault = ctx.accounts.vault.key();
+    token_info.finalized = false;
+    token_info.quorum = settings.quorum;
+    token_info.min_band = settings.min_band;
+    token_info.max_band = settings.max_band;
+    token_info.excluded_accounts = excluded_accounts;
+    token_info.price_scale = state::decimals_scale(ctx.accounts.redeem_mint.decimals)?;
+    token_info.fraction_scale = state::decimals_scale(ctx.accounts.store_mint.decimals)?;
+    // Buckets are created by init_price_buckets for vaults priced at the median
+    token_info.price_mode = settings.price_mode;
+    token_info.price_buckets = Pubkey::default();
+    token_info.median_price = 0;
+    token_info.warm_up_period = settings.warm_up_period;
+    token_info.cooldown_period = settings.cooldown_period;
+    price_account.key = EXTERNAL_ACCOUNT_KEY_V1;
     price_account.price_mint = ctx.accounts.redeem_mint.key();
     Ok(())
 }
\ No newline at end of file
@@ -1,11 +1,25 @@
 pub mod initialize;
 pub mod init_user_info;
+pub mod init_price_buckets;
 pub mod add_votes;
 pub mod remove_votes;
+pub mod activate_votes;
+pub mod delegate_votes;
+pub mod revoke_delegation;
 pub mod set_min_price;
+pub mod set_vote_settings;
+pub mod redeem_votes;
+pub mod settle_offer;
 
 pub use initialize::*;
 pub use init_user_info::*;
+pub use init_price_buckets::*;
 pub use add_votes::*;
 pub use remove_votes::*;
-pub use set_min_price::*;
\ No newline at end of file
+pub use activate_votes::*;
+pub use delegate_votes::*;
+pub use revoke_delegation::*;
+pub use set_min_price::*;
+pub use set_vote_settings::*;
+pub use redeem_votes::*;
+pub use settle_offer::*;
\ No newline at end of file
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{self, Transfer, Burn, Token, Mint, TokenAccount};
+use crate::state::{ExternalPriceAccount, TokenInfo, UserInfo};
+use crate::errors::ErrorCode;
+use crate::vault::{self, VaultState};
+use crate::auction::{self, Redeem, auction_program};
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8, user_bump: u8, redeem_bump: u8)]
+pub struct RedeemVotes<'info> {
+
+    #[account(owner = crate::id())]
+    pub price_account: Account<'info, ExternalPriceAccount>,
+
+    #[account(
+        mut, 
+        constraint = store.mint == store_mint.key(),
+        constraint = store.owner == store_authority_pda.key(), 
+        owner = token::ID
+    )]
+    pub store: Account<'info, TokenAccount>,
+
+    // Every receipt in the account is burned by the signer and redeemed,
+    // whether they voted themselves or were given the receipts
+    #[account(
+        mut,
+        constraint = receipt_account.mint == store_authority_pda.locked_mint,
+        owner = token::ID
+    )]
+    pub receipt_account: Account<'info, TokenAccount>,
+
+    // Whose votes are taken out of the totals, and who gets the rent back once they are all gone
+    #[account(mut)]
+    pub voter: AccountInfo<'info>,
+
+    #[account(
+        mut, 
+        seeds = [b"user".as_ref(), voter.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = user_bump,
+        owner = crate::id()
+    )]
+    pub user_pda: Account<'info, UserInfo>,
+
+    #[account(
+        mut, 
+        seeds = [b"price".as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = pda_bump,
+        owner = crate::id()
+    )]
+    pub store_authority_pda: Account<'info, TokenInfo>,
+
+    // The auction redeems every fraction in the account it is given, so each user's
+    // fractions are moved out of the shared store into this account to be redeemed
+    #[account(
+        init_if_needed, payer = signer,
+        seeds = [b"redeem".as_ref(), price_account.key().as_ref()],
+        bump = redeem_bump,
+        token::mint = store_mint,
+        token::authority = store_authority_pda
+    )]
+    pub redeem_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub store_mint: Account<'info, Mint>,
+
+    #[account(mut, constraint = locked_mint.key() == store_authority_pda.locked_mint)]
+    pub locked_mint: Account<'info, Mint>,
+
+    // Where the proceeds are forwarded to, a token account of the payment mint,
+    // which the auction checks is the one it is paid in
+    #[account(
+        mut,
+        constraint = destination_token_account.owner == signer.key(),
+        constraint = destination_token_account.mint == payment_mint.key()
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    // Accounts passed through to the auction's redeem
+    #[account(mut)]
+    pub auction: AccountInfo<'info>,
+
+    pub settings: AccountInfo<'info>,
+
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payment_treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub redeem_treasury: AccountInfo<'info>,
+
+    pub payment_mint: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub temporary_token_account: AccountInfo<'info>,
+
+    pub vault_pda: AccountInfo<'info>,
+
+    pub vault_program: AccountInfo<'info>,
+
+    pub auction_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+pub fn handler(ctx: Context<RedeemVotes>, pda_bump: u8) -> ProgramResult {
+
+    // Readable accounts
+    let price_account = &ctx.accounts.price_account;
+    let user_info = &ctx.accounts.user_pda;
+    let votes = ctx.accounts.receipt_account.amount;
+
+    user_info.assert_not_delegated()?;
+    if user_info.staked_votes()? < votes {
+        return Err(ErrorCode::NotEnoughVotesToRemove.into());
+    }
+
+    // 1. Make sure the vault is the one priced here, and that it has been combined
+    let vault = vault::load_vault(&ctx.accounts.vault)?;
+    if vault.pricing_lookup_address != price_account.key() || vault.fraction_mint != ctx.accounts.store_mint.key() {
+        return Err(ErrorCode::InvalidVault.into());
+    }
+    if vault.state != VaultState::Combined {
+        return Err(ErrorCode::VaultNotCombined.into());
+    }
+    if *ctx.accounts.auction_program.key != auction_program::ID || !ctx.accounts.auction_program.executable {
+        return Err(ErrorCode::InvalidAuctionProgram.into());
+    }
+
+    let price_key = price_account.key();
+    let bump = [pda_bump];
+    let seeds: &[&[&[u8]]] = &[&[b"price".as_ref(), price_key.as_ref(), ctx.program_id.as_ref(), &bump]];
+
+    // 2. Burn the receipts, move the fractions behind them out of the store and
+    //    redeem them, with the proceeds going straight to the user
+    if votes > 0 {
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(), 
+                Burn {
+                    mint: ctx.accounts.locked_mint.to_account_info(),
+                    to: ctx.accounts.receipt_account.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info()
+                }
+            ), 
+            votes
+        )?;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                Transfer {
+                    from: ctx.accounts.store.to_account_info(),
+                    to: ctx.accounts.redeem_account.to_account_info(),
+                    authority: ctx.accounts.store_authority_pda.to_account_info()
+                },
+                seeds
+            ), 
+            votes
+        )?;
+
+        auction::redeem(
+            CpiContext::new_with_signer(
+                ctx.accounts.auction_program.clone(),
+                Redeem {
+                    auction: ctx.accounts.auction.clone(),
+                    settings: ctx.accounts.settings.clone(),
+                    vault: ctx.accounts.vault.clone(),
+                    payment_treasury: ctx.accounts.payment_treasury.clone(),
+                    redeem_treasury: ctx.accounts.redeem_treasury.clone(),
+                    source_token_account: ctx.accounts.redeem_account.to_account_info(),
+                    destination_token_account: ctx.accounts.destination_token_account.to_account_info(),
+                    fraction_mint: ctx.accounts.store_mint.to_account_info(),
+                    payment_mint: ctx.accounts.payment_mint.clone(),
+                    temporary_token_account: ctx.accounts.temporary_token_account.clone(),
+                    vault_pda: ctx.accounts.vault_pda.clone(),
+                    vault_program: ctx.accounts.vault_program.clone(),
+                    redeemer: ctx.accounts.store_authority_pda.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                seeds
+            )
+        )?;
+    }
+
+    // 3. Take the votes out of the totals, leaving the price of the combined vault as it is
+    let token_info = &mut ctx.accounts.store_authority_pda;
+    let user_info = &mut ctx.accounts.user_pda;
+
+    token_info.finalized = true;
+    let counted_votes = user_info.take_votes(votes)?;
+    token_info.remove_votes(counted_votes, user_info.price)?;
+
+    // 4. Close the voter's record once it is empty, and nobody's votes are delegated to it
+    if user_info.staked_votes()? == 0 && user_info.delegated_votes == 0 {
+        ctx.accounts.user_pda.close(ctx.accounts.voter.clone())?;
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
-use crate::state::{ExternalPriceAccount, TokenInfo, UserInfo};
+use anchor_spl::token::{self, Transfer, Burn, Token, Mint, TokenAccount};
+use crate::state::{self, ExternalPriceAccount, TokenInfo, UserInfo};
 use crate::errors::ErrorCode;
+use crate::vault;
 
 
 #[derive(Accounts)]
@@ -14,6 +15,10 @@ pub struct RemoveVotes<'info> {
     )]
     pub price_account: Account<'info, ExternalPriceAccount>,
 
+    // vault must be the one being priced
+    #[account(constraint = vault.key() == store_authority_pda.vault)]
+    pub vault: AccountInfo<'info>,
+
     #[account(
         mut, 
         constraint = store.mint == store_mint.key(),
@@ -30,9 +35,21 @@ pub struct RemoveVotes<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    // The receipts are the claim on the store, so they are burned from the signer,
+    // who need not be the voter if the receipts have been transferred to them
+    #[account(
+        mut,
+        constraint = receipt_account.mint == store_authority_pda.locked_mint,
+        owner = token::ID
+    )]
+    pub receipt_account: Account<'info, TokenAccount>,
+
+    // Whose votes are taken back, the signer's own unless they were given the receipts
+    pub voter: AccountInfo<'info>,
+
     #[account(
         mut, 
-        seeds = [b"user".as_ref(), signer.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        seeds = [b"user".as_ref(), voter.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
         bump = user_bump,
         owner = crate::id()
     )]
@@ -48,6 +65,9 @@ pub struct RemoveVotes<'info> {
 
     pub store_mint: Account<'info, Mint>,
 
+    #[account(mut, constraint = locked_mint.key() == store_authority_pda.locked_mint)]
+    pub locked_mint: Account<'info, Mint>,
+
     pub signer: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
@@ -55,7 +75,7 @@ pub struct RemoveVotes<'info> {
 }
 
 
-pub fn handler(ctx: Context<RemoveVotes>, pda_bump: u8, votes: u64) -> ProgramResult {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, RemoveVotes<'info>>, pda_bump: u8, votes: u64) -> ProgramResult {
     if votes == 0 {
         return Err(ErrorCode::AmountIsZero.into());
     }
@@ -69,44 +89,50 @@ pub fn handler(ctx: Context<RemoveVotes>, pda_bump: u8, votes: u64) -> ProgramRe
     let user_token_account = &ctx.accounts.user_token_account;
     let store_mint = &ctx.accounts.store_mint;
 
-    if user_info.votes < votes {
+    // Delegated votes count at the delegate's price until the delegation is revoked
+    user_info.assert_not_delegated()?;
+    if user_info.staked_votes()? < votes {
         return Err(ErrorCode::NotEnoughVotesToRemove.into());
     }
 
-    user_info.votes -= votes;
-    token_info.total_votes -= votes;
-
-    token_info.reserve_total -= match (votes as u128).checked_mul(user_info.price as u128) {
-        Some(val) => match (val).checked_div(1e9 as u128) {
-            Some(val) => val as u64,
-            None => return Err(ErrorCode::NumericalOverflowError.into()),
-        },
-        None => return Err(ErrorCode::NumericalOverflowError.into()),
-    };
-    
-    let half_supply = match store_mint.supply.checked_div(2) {
-        Some(val) => val,
-        None => return Err(ErrorCode::NumericalOverflowError.into()),
-    };
+    // Votes are frozen once the vault has been combined, but voters can still take back their tokens
+    if !token_info.finalized && vault::is_finalized(&ctx.accounts.vault, &price_account.key())? {
+        token_info.finalized = true;
+    }
 
-    if token_info.total_votes <= half_supply {
-        price_account.allowed_to_combine = false;
+    // Until then votes are locked for the cooldown after the voter's last vote,
+    // and any which have warmed up since are counted before they are taken back
+    let (price_buckets, excluded_accounts) = token_info.split_price_buckets(ctx.remaining_accounts);
+    if !token_info.finalized {
+        let now = state::current_timestamp()?;
+        token_info.assert_cooldown_passed(user_info, now)?;
+        token_info.activate_votes(user_info, price_buckets, now)?;
     }
 
-    if token_info.total_votes > 0 {
-        price_account.price_per_share = match (token_info.reserve_total as u128).checked_mul(1e9 as u128) {
-            Some(val) => match val.checked_div(token_info.total_votes as u128) {
-                Some(val) => std::cmp::max(token_info.min_price, val as u64),
-                None => return Err(ErrorCode::NumericalOverflowError.into()),
-            },
-            None => return Err(ErrorCode::NumericalOverflowError.into()),
-        };
-    } else {
-        price_account.price_per_share = token_info.min_price;
+    let counted_votes = user_info.take_votes(votes)?;
+    token_info.remove_votes(counted_votes, user_info.price)?;
+
+    // The price (and buckets) the vault was combined at are kept once the votes are frozen
+    if !token_info.finalized {
+        token_info.move_bucket_votes(price_buckets, counted_votes, user_info.price, 0, user_info.price)?;
+        let circulating_supply = token_info.circulating_supply(store_mint, excluded_accounts)?;
+        price_account.allowed_to_combine = token_info.has_quorum(circulating_supply)?;
+        price_account.price_per_share = token_info.price_per_share()?;
     }
-    
 
-    // transfer tokens from store to user_token_account
+    // burn the receipts, then transfer tokens from store to user_token_account
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(), 
+            Burn {
+                mint: ctx.accounts.locked_mint.to_account_info(),
+                to: ctx.accounts.receipt_account.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info()
+            }
+        ), 
+        votes
+    )?;
+
     token::transfer(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(), 
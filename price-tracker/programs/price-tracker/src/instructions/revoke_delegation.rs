@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::state::{Delegation, ExternalPriceAccount, TokenInfo, UserInfo};
+use crate::errors::ErrorCode;
+use crate::vault;
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8, user_bump: u8, delegate_bump: u8)]
+pub struct RevokeDelegation<'info> {
+
+    #[account(mut, owner = crate::id())]
+    pub price_account: Account<'info, ExternalPriceAccount>,
+
+    // vault must be the one being priced
+    #[account(constraint = vault.key() == store_authority_pda.vault)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut, 
+        seeds = [b"user".as_ref(), signer.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = user_bump,
+        owner = crate::id()
+    )]
+    pub user_pda: Account<'info, UserInfo>,
+
+    pub delegate: AccountInfo<'info>,
+
+    #[account(
+        mut, 
+        seeds = [b"user".as_ref(), delegate.key().as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = delegate_bump,
+        owner = crate::id()
+    )]
+    pub delegate_pda: Account<'info, UserInfo>,
+
+    #[account(
+        mut,
+        constraint = delegation.delegator == signer.key(),
+        has_one = delegate,
+        close = signer
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(
+        mut, 
+        seeds = [b"price".as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = pda_bump,
+        owner = crate::id()
+    )]
+    pub store_authority_pda: Account<'info, TokenInfo>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+}
+
+// The votes go back to the delegator's own price, after which they can be removed as usual.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, RevokeDelegation<'info>>) -> ProgramResult {
+
+    // Writable accounts
+    let token_info = &mut ctx.accounts.store_authority_pda;
+    let user_info = &mut ctx.accounts.user_pda;
+    let delegate_info = &mut ctx.accounts.delegate_pda;
+    let price_account = &mut ctx.accounts.price_account;
+
+    // Delegations can be revoked at any time, but the price is kept once the votes are frozen
+    if !token_info.finalized && vault::is_finalized(&ctx.accounts.vault, &price_account.key())? {
+        token_info.finalized = true;
+    }
+
+    let votes = ctx.accounts.delegation.votes;
+    let (price_buckets, _) = token_info.split_price_buckets(ctx.remaining_accounts);
+    token_info.move_votes(price_buckets, votes, delegate_info.price, user_info.price)?;
+    delegate_info.delegated_votes = match delegate_info.delegated_votes.checked_sub(votes) {
+        Some(val) => val,
+        None => return Err(ErrorCode::NumericalOverflowError.into()),
+    };
+    user_info.delegate = None;
+
+    if !token_info.finalized {
+        price_account.price_per_share = token_info.price_per_share()?;
+    }
+    Ok(())
+}
\ No newline at end of file
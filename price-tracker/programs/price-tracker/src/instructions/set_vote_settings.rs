@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{ExternalPriceAccount, TokenInfo};
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8)]
+pub struct SetVoteSettings<'info> {
+
+    #[account(mut, owner = crate::id())]
+    pub price_account: Account<'info, ExternalPriceAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"price".as_ref(), price_account.key().as_ref(), crate::id().as_ref()], 
+        bump = pda_bump,
+        has_one = authority
+    )]
+    pub store_authority_pda: Account<'info, TokenInfo>,
+
+    #[account(constraint = store_mint.key() == store_authority_pda.mint)]
+    pub store_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>
+
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SetVoteSettings<'info>>,
+    quorum: u64,
+    min_band: u64,
+    max_band: u64,
+    warm_up_period: u64,
+    cooldown_period: u64
+) -> ProgramResult {
+    TokenInfo::assert_valid_settings(quorum, min_band, max_band)?;
+
+    let token_info = &mut ctx.accounts.store_authority_pda;
+    token_info.quorum = quorum;
+    token_info.min_band = min_band;
+    token_info.max_band = max_band;
+    token_info.warm_up_period = warm_up_period;
+    token_info.cooldown_period = cooldown_period;
+
+    // The new quorum applies straight away, unless the votes are already frozen
+    if !token_info.finalized {
+        let circulating_supply = token_info.circulating_supply(&ctx.accounts.store_mint, ctx.remaining_accounts)?;
+        let price_account = &mut ctx.accounts.price_account;
+        price_account.allowed_to_combine = token_info.has_quorum(circulating_supply)?;
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::auction;
+use crate::state::ExternalPriceAccount;
+
+#[derive(Accounts)]
+pub struct SettleOffer<'info> {
+
+    #[account(mut)]
+    pub price_account: Account<'info, ExternalPriceAccount>,
+
+    // Only the auction program can sign for its authority, which is also the vault's authority.
+    #[account(address = auction::find_authority().0)]
+    pub auction_authority: Signer<'info>
+
+}
+
+pub fn handler(ctx: Context<SettleOffer>, price_per_share: u64) -> ProgramResult {
+    let price_account = &mut ctx.accounts.price_account;
+    price_account.price_per_share = price_per_share;
+    price_account.allowed_to_combine = true;
+    Ok(())
+}
@@ -3,32 +3,106 @@ use anchor_lang::prelude::*;
 pub mod errors;
 pub mod state;
 pub mod instructions;
+pub mod vault;
+pub mod auction;
 
 use instructions::*;
+use state::VoteSettings;
 
 declare_id!("D8K3mcxvwDE7ykDPhL6xGXfkWXhbB1YS2nH3MCS1WmsD");
 
+// The cluster features only pick the token vault deployment, see `vault::token_vault_program`.
+#[cfg(not(any(feature = "localnet", feature = "devnet", feature = "mainnet")))]
+compile_error!("One of the `localnet`, `devnet` or `mainnet` features must be enabled");
+
+#[cfg(any(
+    all(feature = "localnet", feature = "devnet"),
+    all(feature = "localnet", feature = "mainnet"),
+    all(feature = "devnet", feature = "mainnet")
+))]
+compile_error!("Only one cluster feature can be enabled, build with `--no-default-features` to pick another cluster than `localnet`");
+
 #[program]
 pub mod price_tracker {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, pda_bump: u8, min_price: u64) -> ProgramResult {
-        instructions::initialize::handler(ctx, min_price)
+    // The quorum and price band are in basis points, of the supply and of the current price.
+    // Vaults priced at the median need an account created for their buckets and set up with init_price_buckets before voting,
+    // and passed ahead of any other remaining accounts of the instructions which move votes.
+    // New votes only count after the warm-up period, and none can be removed for the cooldown
+    // period after a voter's last vote, both in seconds.
+    pub fn initialize<'info>(
+        ctx: Context<'_, '_, '_, 'info, Initialize<'info>>,
+        pda_bump: u8,
+        min_price: u64,
+        settings: VoteSettings
+    ) -> ProgramResult {
+        instructions::initialize::handler(ctx, min_price, settings)
     }
 
     pub fn init_user_info(ctx: Context<InitUserInfo>, user_bump: u8) -> ProgramResult {
         Ok(())
     }
 
+    pub fn init_price_buckets(ctx: Context<InitPriceBuckets>, pda_bump: u8) -> ProgramResult {
+        instructions::init_price_buckets::handler(ctx)
+    }
+
     pub fn set_min_price(ctx: Context<SetMinPrice>, pda_bump: u8, min_price: u64) -> ProgramResult {
         instructions::set_min_price::handler(ctx, min_price)
     }
 
-    pub fn add_vote(ctx: Context<AddVotes>, pda_bump: u8, user_bump: u8, votes: u64, price: u64) -> ProgramResult {
-        instructions::add_votes::handler(ctx, votes, price)
+    pub fn set_vote_settings<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetVoteSettings<'info>>,
+        pda_bump: u8,
+        quorum: u64,
+        min_band: u64,
+        max_band: u64,
+        warm_up_period: u64,
+        cooldown_period: u64
+    ) -> ProgramResult {
+        instructions::set_vote_settings::handler(ctx, quorum, min_band, max_band, warm_up_period, cooldown_period)
     }
 
-    pub fn remove_vote(ctx: Context<RemoveVotes>, pda_bump: u8, user_bump: u8, votes: u64) -> ProgramResult {
+    pub fn add_vote<'info>(ctx: Context<'_, '_, '_, 'info, AddVotes<'info>>, pda_bump: u8, user_bump: u8, votes: u64, price: u64) -> ProgramResult {
+        instructions::add_votes::handler(ctx, pda_bump, votes, price)
+    }
+
+    pub fn remove_vote<'info>(ctx: Context<'_, '_, '_, 'info, RemoveVotes<'info>>, pda_bump: u8, user_bump: u8, votes: u64) -> ProgramResult {
         instructions::remove_votes::handler(ctx, pda_bump, votes)
     }
+
+    // Count a voter's votes once they have warmed up, without waiting for their next vote.
+    pub fn activate_votes<'info>(ctx: Context<'_, '_, '_, 'info, ActivateVotes<'info>>, pda_bump: u8, user_bump: u8) -> ProgramResult {
+        instructions::activate_votes::handler(ctx)
+    }
+
+    // Let another voter's price apply to the signer's counted votes, until the delegation is revoked.
+    // Delegated votes can't be added to or removed, they follow the delegate's price instead.
+    pub fn delegate_votes<'info>(
+        ctx: Context<'_, '_, '_, 'info, DelegateVotes<'info>>,
+        pda_bump: u8,
+        user_bump: u8,
+        delegate_bump: u8,
+        delegation_bump: u8
+    ) -> ProgramResult {
+        instructions::delegate_votes::handler(ctx)
+    }
+
+    pub fn revoke_delegation<'info>(ctx: Context<'_, '_, '_, 'info, RevokeDelegation<'info>>, pda_bump: u8, user_bump: u8, delegate_bump: u8) -> ProgramResult {
+        instructions::revoke_delegation::handler(ctx)
+    }
+
+    // Once the vault has been combined and auctioned, redeem the fractions behind the
+    // signer's receipts through the auction program straight from the store.
+    // The voter's record is closed once it has no votes left, and none are delegated to it.
+    pub fn redeem_votes(ctx: Context<RedeemVotes>, pda_bump: u8, user_bump: u8, redeem_bump: u8) -> ProgramResult {
+        instructions::redeem_votes::handler(ctx, pda_bump)
+    }
+
+    // Price the vault at an offer which a majority of its fractions accepted, for the auction
+    // program to combine it straight away. Only the auction program can sign for this.
+    pub fn settle_offer(ctx: Context<SettleOffer>, price_per_share: u64) -> ProgramResult {
+        instructions::settle_offer::handler(ctx, price_per_share)
+    }
 }
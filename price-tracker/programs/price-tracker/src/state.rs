@@ -1,20 +1,436 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::clock::Clock;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::errors::ErrorCode;
+
+// Matches `Key::ExternalAccountKeyV1` in the token vault program.
+pub const EXTERNAL_ACCOUNT_KEY_V1: u8 = 2;
+
+// The quorum and price band are given in basis points.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Token accounts whose fractions are not in circulation, such as the vault's fraction treasury.
+pub const MAX_EXCLUDED_ACCOUNTS: usize = 4;
+
+// Prices are bucketed on a log scale, with 2^PRICE_BUCKET_BITS buckets between each power of two,
+// so the prices in a bucket are all within 1/2^PRICE_BUCKET_BITS (about 3%) of its lowest price.
+pub const PRICE_BUCKET_BITS: u32 = 5;
+pub const PRICE_BUCKETS: usize = 64 << PRICE_BUCKET_BITS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum PriceMode {
+    // The vote weighted average of every price
+    Mean,
+    // The price which half of the votes are at or below, kept in price buckets
+    Median,
+}
+
+// How a price account takes votes, besides its minimum price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VoteSettings {
+    pub quorum: u64,
+    pub min_band: u64,
+    pub max_band: u64,
+    pub price_mode: PriceMode,
+    pub warm_up_period: u64,
+    pub cooldown_period: u64,
+}
 
 #[account]
 pub struct TokenInfo {
-    pub reserve_total: u64,
+    // The exact sum of votes * price over every voter.
+    pub reserve_total: u128,
     pub total_votes: u64,
     pub min_price: u64,
     pub mint: Pubkey,
     pub store: Pubkey,
     pub locked_mint: Pubkey,
     pub authority: Pubkey,
+    // The token vault being priced. Votes are frozen once it has been combined.
+    pub vault: Pubkey,
+    pub finalized: bool,
+    // Share of the supply which must vote before the vault can be combined,
+    // and how far below and above the current price a new vote may be.
+    pub quorum: u64,
+    pub min_band: u64,
+    pub max_band: u64,
+    // Left out of the supply the quorum is taken of, passed as remaining accounts on each vote.
+    pub excluded_accounts: Vec<Pubkey>,
+    // Votes are priced as if the fraction and price mints had the same decimals,
+    // 10^decimals of each mint converts that price into the price per share.
+    pub price_scale: u128,
+    pub fraction_scale: u128,
+    pub price_mode: PriceMode,
+    // Only created for vaults priced at the median, along with the last median taken of them.
+    pub price_buckets: Pubkey,
+    pub median_price: u64,
+    // In seconds, how long after a vote before it counts, and before any votes can be removed.
+    pub warm_up_period: u64,
+    pub cooldown_period: u64,
+}
+
+impl TokenInfo {
+    pub fn assert_valid_settings(quorum: u64, min_band: u64, max_band: u64) -> ProgramResult {
+        if quorum == 0 || quorum > BPS_DENOMINATOR {
+            return Err(ErrorCode::InvalidQuorum.into());
+        }
+        if min_band > BPS_DENOMINATOR || max_band < BPS_DENOMINATOR {
+            return Err(ErrorCode::InvalidPriceBand.into());
+        }
+        Ok(())
+    }
+
+    pub fn space() -> usize {
+        32 + 32 * 5 + 1 + 24 + 4 + MAX_EXCLUDED_ACCOUNTS * 32 + 32 + 1 + 32 + 8 + 16
+    }
+
+    // The supply less the fractions held by the excluded accounts, which have to be
+    // passed in the order they were recorded in
+    pub fn circulating_supply<'info>(&self, mint: &Account<'info, Mint>, accounts: &[AccountInfo<'info>]) -> Result<u64, ProgramError> {
+        if accounts.len() != self.excluded_accounts.len() {
+            return Err(ErrorCode::InvalidExcludedAccounts.into());
+        }
+
+        let mut supply = mint.supply;
+        for (key, info) in self.excluded_accounts.iter().zip(accounts.iter()) {
+            if info.key != key {
+                return Err(ErrorCode::InvalidExcludedAccounts.into());
+            }
+            supply = match supply.checked_sub(excluded_balance(mint, info)?) {
+                Some(val) => val,
+                None => return Err(ErrorCode::NumericalOverflowError.into()),
+            };
+        }
+        Ok(supply)
+    }
+
+    pub fn add_votes(&mut self, votes: u64, price: u64) -> ProgramResult {
+        self.reserve_total = match self.reserve_total.checked_add(vote_weight(votes, price)?) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        self.total_votes = match self.total_votes.checked_add(votes) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+    pub fn remove_votes(&mut self, votes: u64, price: u64) -> ProgramResult {
+        self.reserve_total = match self.reserve_total.checked_sub(vote_weight(votes, price)?) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        self.total_votes = match self.total_votes.checked_sub(votes) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+    // The vote weighted average or median price, never below the minimum price
+    pub fn current_price(&self) -> Result<u64, ProgramError> {
+        if self.total_votes == 0 {
+            return Ok(self.min_price);
+        }
+        let price = match self.price_mode {
+            PriceMode::Mean => match self.reserve_total.checked_div(self.total_votes as u128) {
+                Some(val) => val as u64,
+                None => return Err(ErrorCode::NumericalOverflowError.into()),
+            },
+            PriceMode::Median => self.median_price,
+        };
+        Ok(std::cmp::max(self.min_price, price))
+    }
+
+    // Vaults priced at the median are given their price buckets as the first of the
+    // remaining accounts, ahead of the accounts left out of the quorum.
+    pub fn split_price_buckets<'a, 'info>(
+        &self,
+        accounts: &'a [AccountInfo<'info>]
+    ) -> (Option<&'a AccountInfo<'info>>, &'a [AccountInfo<'info>]) {
+        match (self.price_mode, accounts.split_first()) {
+            (PriceMode::Median, Some((price_buckets, rest))) => (Some(price_buckets), rest),
+            _ => (None, accounts),
+        }
+    }
+
+    // Moves a voter's votes from their old price to their new one in the price buckets,
+    // and takes the median again. Vaults priced at the mean have no buckets to update.
+    pub fn move_bucket_votes<'info>(
+        &mut self,
+        price_buckets: Option<&AccountInfo<'info>>,
+        old_votes: u64,
+        old_price: u64,
+        new_votes: u64,
+        new_price: u64
+    ) -> ProgramResult {
+        if self.price_mode != PriceMode::Median {
+            return Ok(());
+        }
+        let price_buckets = match price_buckets {
+            Some(info) if self.price_buckets != Pubkey::default() && info.key() == self.price_buckets => info,
+            _ => return Err(ErrorCode::InvalidPriceBuckets.into()),
+        };
+
+        let loader: AccountLoader<PriceBuckets> = AccountLoader::try_from(price_buckets)?;
+        let mut buckets = loader.load_mut()?;
+        buckets.remove_votes(old_votes, old_price)?;
+        buckets.add_votes(new_votes, new_price)?;
+        self.median_price = buckets.median_price(self.total_votes)?;
+        Ok(())
+    }
+
+    // Counts a voter's pending votes once they have warmed up, along with the rest of
+    // their votes and those delegated to them which move over to the pending price.
+    // The votes of a delegator can't change, they count at their delegate's price.
+    pub fn activate_votes<'info>(&mut self, user_info: &mut UserInfo, price_buckets: Option<&AccountInfo<'info>>, now: u64) -> ProgramResult {
+        if user_info.delegate.is_some() {
+            return Ok(());
+        }
+        let active_from = match user_info.last_vote_timestamp.checked_add(self.warm_up_period) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        if now < active_from {
+            return Ok(());
+        }
+
+        let old_weight = user_info.weight()?;
+        let old_price = user_info.price;
+        self.remove_votes(old_weight, old_price)?;
+
+        user_info.price = user_info.pending_price;
+        user_info.votes = match user_info.votes.checked_add(user_info.pending_votes) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        user_info.pending_votes = 0;
+
+        let new_weight = user_info.weight()?;
+        self.add_votes(new_weight, user_info.price)?;
+        self.move_bucket_votes(price_buckets, old_weight, old_price, new_weight, user_info.price)
+    }
+
+    // Moves votes counted at one price over to another, as when they are delegated or the
+    // delegation is revoked. The buckets are left as they are once the votes are frozen.
+    pub fn move_votes<'info>(&mut self, price_buckets: Option<&AccountInfo<'info>>, votes: u64, old_price: u64, new_price: u64) -> ProgramResult {
+        self.remove_votes(votes, old_price)?;
+        self.add_votes(votes, new_price)?;
+        if self.finalized {
+            return Ok(());
+        }
+        self.move_bucket_votes(price_buckets, votes, old_price, votes, new_price)
+    }
+
+    pub fn assert_cooldown_passed(&self, user_info: &UserInfo, now: u64) -> ProgramResult {
+        let unlocked_from = match user_info.last_vote_timestamp.checked_add(self.cooldown_period) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        if now < unlocked_from {
+            return Err(ErrorCode::VotesLocked.into());
+        }
+        Ok(())
+    }
+
+    // The current price in base units of the price mint per base unit of the fraction mint
+    pub fn price_per_share(&self) -> Result<u64, ProgramError> {
+        let scaled = match (self.current_price()? as u128).checked_mul(self.price_scale) {
+            Some(val) => match val.checked_div(self.fraction_scale) {
+                Some(val) => val,
+                None => return Err(ErrorCode::NumericalOverflowError.into()),
+            },
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        if scaled > u64::MAX as u128 {
+            return Err(ErrorCode::NumericalOverflowError.into());
+        }
+        Ok(scaled as u64)
+    }
+
+    // Whether the votes make up more than the quorum of the supply
+    pub fn has_quorum(&self, supply: u64) -> Result<bool, ProgramError> {
+        let required = match (supply as u128).checked_mul(self.quorum as u128) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        let votes = match (self.total_votes as u128).checked_mul(BPS_DENOMINATOR as u128) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        Ok(votes > required)
+    }
+}
+
+// Vote weights by price, so that the median can be found without reading every voter
+#[account(zero_copy)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct PriceBuckets {
+    pub price_account: Pubkey,
+    pub weights: [u64; PRICE_BUCKETS],
+    // The sum of votes * price in each bucket, the median is the average of its bucket
+    pub reserves: [u128; PRICE_BUCKETS],
+}
+
+impl PriceBuckets {
+    pub fn space() -> usize {
+        32 + PRICE_BUCKETS * (8 + 16)
+    }
+
+    // The leading bits of the price, so each bucket spans a fixed fraction of its prices
+    pub fn bucket(price: u64) -> usize {
+        if price < 1 << PRICE_BUCKET_BITS {
+            return price as usize;
+        }
+        let octave = 63 - price.leading_zeros();
+        let step = (price >> (octave - PRICE_BUCKET_BITS)) as usize - (1 << PRICE_BUCKET_BITS);
+        ((octave as usize) << PRICE_BUCKET_BITS) + step
+    }
+
+    pub fn add_votes(&mut self, votes: u64, price: u64) -> ProgramResult {
+        let bucket = PriceBuckets::bucket(price);
+        self.weights[bucket] = match self.weights[bucket].checked_add(votes) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        self.reserves[bucket] = match self.reserves[bucket].checked_add(vote_weight(votes, price)?) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+    pub fn remove_votes(&mut self, votes: u64, price: u64) -> ProgramResult {
+        let bucket = PriceBuckets::bucket(price);
+        self.weights[bucket] = match self.weights[bucket].checked_sub(votes) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        self.reserves[bucket] = match self.reserves[bucket].checked_sub(vote_weight(votes, price)?) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+    // The average price of the bucket holding the middle vote. It is an approximation of the exact
+    // weighted median, which is in the same bucket, so the two are less than 1/2^PRICE_BUCKET_BITS
+    // of the exact median apart. Prices below 2^PRICE_BUCKET_BITS have a bucket each.
+    pub fn median_price(&self, total_votes: u64) -> Result<u64, ProgramError> {
+        let mut votes: u64 = 0;
+        for bucket in 0..PRICE_BUCKETS {
+            let weight = self.weights[bucket];
+            if weight == 0 {
+                continue;
+            }
+            votes = match votes.checked_add(weight) {
+                Some(val) => val,
+                None => return Err(ErrorCode::NumericalOverflowError.into()),
+            };
+            if (votes as u128) * 2 >= total_votes as u128 {
+                return match self.reserves[bucket].checked_div(weight as u128) {
+                    Some(val) => Ok(val as u64),
+                    None => Err(ErrorCode::NumericalOverflowError.into()),
+                };
+            }
+        }
+        Ok(0)
+    }
+}
+
+pub fn vote_weight(votes: u64, price: u64) -> Result<u128, ProgramError> {
+    match (votes as u128).checked_mul(price as u128) {
+        Some(val) => Ok(val),
+        None => Err(ErrorCode::NumericalOverflowError.into()),
+    }
+}
+
+pub fn decimals_scale(decimals: u8) -> Result<u128, ProgramError> {
+    match 10u128.checked_pow(decimals as u32) {
+        Some(val) => Ok(val),
+        None => Err(ErrorCode::NumericalOverflowError.into()),
+    }
+}
+
+pub fn excluded_balance<'info>(mint: &Account<'info, Mint>, info: &AccountInfo<'info>) -> Result<u64, ProgramError> {
+    let account: Account<TokenAccount> = Account::try_from(info)?;
+    if account.mint != mint.key() {
+        return Err(ErrorCode::InvalidExcludedAccounts.into());
+    }
+    Ok(account.amount)
 }
 
 #[account]
 pub struct UserInfo {
     pub price: u64,
     pub votes: u64,
+    // Staked by the latest votes, but not counted until the warm-up period has passed.
+    pub pending_price: u64,
+    pub pending_votes: u64,
+    pub last_vote_timestamp: u64,
+    // Votes delegated to this voter, which count at their price.
+    pub delegated_votes: u64,
+    // Who this voter's votes are delegated to, if anyone.
+    pub delegate: Option<Pubkey>,
+}
+
+impl UserInfo {
+    pub fn space() -> usize {
+        6 * 8 + 1 + 32
+    }
+
+    // The votes counted at this voter's price
+    pub fn weight(&self) -> Result<u64, ProgramError> {
+        match self.votes.checked_add(self.delegated_votes) {
+            Some(val) => Ok(val),
+            None => Err(ErrorCode::NumericalOverflowError.into()),
+        }
+    }
+
+    pub fn assert_not_delegated(&self) -> ProgramResult {
+        if self.delegate.is_some() {
+            return Err(ErrorCode::VotesDelegated.into());
+        }
+        Ok(())
+    }
+
+    pub fn staked_votes(&self) -> Result<u64, ProgramError> {
+        match self.votes.checked_add(self.pending_votes) {
+            Some(val) => Ok(val),
+            None => Err(ErrorCode::NumericalOverflowError.into()),
+        }
+    }
+
+    // Takes votes out of the pending votes first, returning how many of those taken had been counted
+    pub fn take_votes(&mut self, votes: u64) -> Result<u64, ProgramError> {
+        let pending = std::cmp::min(votes, self.pending_votes);
+        self.pending_votes -= pending;
+        let counted = votes - pending;
+        self.votes = match self.votes.checked_sub(counted) {
+            Some(val) => val,
+            None => return Err(ErrorCode::NotEnoughVotesToRemove.into()),
+        };
+        Ok(counted)
+    }
+}
+
+// A voter's counted votes delegated to another voter, until the delegator revokes them.
+#[account]
+pub struct Delegation {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub votes: u64,
+}
+
+impl Delegation {
+    pub fn space() -> usize {
+        2 * 32 + 8
+    }
+}
+
+pub fn current_timestamp() -> Result<u64, ProgramError> {
+    Ok(Clock::get()?.unix_timestamp as u64)
 }
 
 #[account]
@@ -23,4 +439,71 @@ pub struct ExternalPriceAccount {
     pub price_per_share: u64,
     pub price_mint: Pubkey,
     pub allowed_to_combine: bool
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_buckets() -> Box<PriceBuckets> {
+        Box::new(PriceBuckets {
+            price_account: Pubkey::default(),
+            weights: [0; PRICE_BUCKETS],
+            reserves: [0; PRICE_BUCKETS],
+        })
+    }
+
+    // The lowest price which half of the votes are at or below
+    fn exact_median(votes: &[(u64, u64)]) -> u64 {
+        let mut sorted = votes.to_vec();
+        sorted.sort_by_key(|&(_, price)| price);
+        let total: u128 = sorted.iter().map(|&(votes, _)| votes as u128).sum();
+        let mut counted: u128 = 0;
+        for (votes, price) in sorted {
+            counted += votes as u128;
+            if counted * 2 >= total {
+                return price;
+            }
+        }
+        0
+    }
+
+    #[test]
+    fn median_price_is_exact_for_a_single_price_in_its_bucket() {
+        let mut buckets = empty_buckets();
+        buckets.add_votes(2_000_000_000, 10_000_000_000).unwrap();
+        buckets.add_votes(1_000_000_000, 12_000_000_000).unwrap();
+        buckets.add_votes(2_000_000_000, 40_000_000_000).unwrap();
+        assert_eq!(buckets.median_price(5_000_000_000).unwrap(), 12_000_000_000);
+
+        buckets.remove_votes(2_000_000_000, 40_000_000_000).unwrap();
+        assert_eq!(buckets.median_price(3_000_000_000).unwrap(), 10_000_000_000);
+    }
+
+    #[test]
+    fn median_price_is_within_a_bucket_of_the_exact_median() {
+        // A fixed linear congruential generator, so any failure can be reproduced
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            seed >> 16
+        };
+
+        for _ in 0..200 {
+            let mut buckets = empty_buckets();
+            let mut votes = Vec::new();
+            let mut total_votes: u64 = 0;
+            for _ in 0..1 + next() % 20 {
+                let vote = (1 + next() % 1_000, 1 + (next() >> (next() % 48)));
+                buckets.add_votes(vote.0, vote.1).unwrap();
+                total_votes += vote.0;
+                votes.push(vote);
+            }
+
+            let exact = exact_median(&votes);
+            let median = buckets.median_price(total_votes).unwrap();
+            assert_eq!(PriceBuckets::bucket(median), PriceBuckets::bucket(exact));
+            assert!(((median as i128 - exact as i128).abs() << PRICE_BUCKET_BITS) < exact as i128);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{borsh::try_from_slice_unchecked, system_program};
+use std::result::Result;
+
+use crate::errors::ErrorCode;
+
+// Matches `Key::VaultV1` in the token vault program.
+pub const VAULT_KEY_V1: u8 = 3;
+pub const MAX_VAULT_SIZE: usize = 1 + 32 + 32 + 32 + 32 + 1 + 32 + 1 + 32 + 1 + 1 + 8;
+
+// The token vault address depends on the cluster the tracker is built for,
+// which is chosen with the `localnet`, `devnet` or `mainnet` feature.
+pub mod token_vault_program {
+    use anchor_lang::prelude::*;
+
+    #[cfg(feature = "localnet")]
+    declare_id!("FMcRJeEKBjD1LU5UyBHvorpep4hVg48ff2C36NX83vtY");
+
+    #[cfg(feature = "devnet")]
+    declare_id!("vau1zxA2LbssAUEF7Gpw91zMM1LvXrvpzJtmZ58rPsn");
+
+    #[cfg(feature = "mainnet")]
+    declare_id!("vau1zxA2LbssAUEF7Gpw91zMM1LvXrvpzJtmZ58rPsn");
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum VaultState {
+    Inactive,
+    Active,
+    Combined,
+    Deactivated,
+}
+
+// The token vault which a price account prices, read only.
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Vault {
+    pub key: u8,
+    pub token_program: Pubkey,
+    pub fraction_mint: Pubkey,
+    pub authority: Pubkey,
+    pub fraction_treasury: Pubkey,
+    pub redeem_treasury: Pubkey,
+    pub allow_further_share_creation: bool,
+    pub pricing_lookup_address: Pubkey,
+    pub token_type_count: u8,
+    pub state: VaultState,
+    pub locked_price_per_share: u64,
+}
+
+pub fn load_vault(vault: &AccountInfo) -> Result<Vault, ProgramError> {
+    if *vault.owner != token_vault_program::ID || vault.data_len() != MAX_VAULT_SIZE {
+        return Err(ErrorCode::InvalidVault.into());
+    }
+    let vault: Vault = try_from_slice_unchecked(&vault.try_borrow_data()?)?;
+    if vault.key != VAULT_KEY_V1 {
+        return Err(ErrorCode::InvalidVault.into());
+    }
+    Ok(vault)
+}
+
+// The vault does not exist yet when its price account is initialised, it is created
+// right after by the token vault program.
+pub fn assert_not_created(vault: &AccountInfo) -> ProgramResult {
+    if *vault.owner != system_program::ID || !vault.data_is_empty() {
+        return Err(ErrorCode::InvalidVault.into());
+    }
+    Ok(())
+}
+
+// Votes are final once the vault has left the Active state. Until the vault has been created
+// it cannot have been combined, once it has it must be the one priced by the price account.
+pub fn is_finalized(vault: &AccountInfo, price_account: &Pubkey) -> Result<bool, ProgramError> {
+    if *vault.owner == system_program::ID && vault.data_is_empty() {
+        return Ok(false);
+    }
+    let vault = load_vault(vault)?;
+    if vault.pricing_lookup_address != *price_account {
+        return Err(ErrorCode::InvalidVault.into());
+    }
+    Ok(vault.state == VaultState::Combined || vault.state == VaultState::Deactivated)
+}
\ No newline at end of file
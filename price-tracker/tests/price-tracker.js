@@ -20,6 +20,8 @@ describe('price-tracker', () => {
 	const storeTokenAccount = anchor.web3.Keypair.generate();
     const priceAccount = anchor.web3.Keypair.generate();
 	console.log(priceAccount.publicKey.toBase58());
+	// Only recorded by the price tracker, the vault is never created (or combined) here
+	const vault = anchor.web3.Keypair.generate();
 	var userTokenAccount;
 
 	// Mints
@@ -33,6 +35,9 @@ describe('price-tracker', () => {
 
 	// Params
 	const minPrice = new BN(10 * 10**DECIMALS);
+	const quorum = new BN(5_000); // More than half of the supply
+	const minBand = new BN(2_000); // 1/5th of the current price
+	const maxBand = new BN(50_000); // 5x the current price
 
 	const getTokenAccountBalance = async (address) => {
 		let res = await connection.getTokenAccountBalance(address);
@@ -48,12 +53,31 @@ describe('price-tracker', () => {
 		);
 	}
 
+	// Only created for vaults priced at the median, and passed as the first remaining account
+	const initPriceBuckets = async (priceAcc, pda, pdaBump, priceBuckets) => {
+		await program.rpc.initPriceBuckets(pdaBump, {
+			accounts: {
+				priceAccount: priceAcc.publicKey,
+				storeAuthorityPda: pda,
+				priceBuckets: priceBuckets.publicKey,
+			},
+			instructions: [await program.account.priceBuckets.createInstruction(priceBuckets)],
+			signers: [priceBuckets]
+		});
+	}
+
+	const getReceiptAccount = async (owner) => {
+		return (await lockedMint.getOrCreateAssociatedAccountInfo(owner)).address;
+	}
+
 	const addVotes = async (votes, price, fromAccount, owner=payer, priceAcc=priceAccount) => {
 		votes = new BN((votes * 10**DECIMALS).toString());
 		price = new BN((price * 10**DECIMALS).toString());
 
+		let receiptAccount = await getReceiptAccount(owner.publicKey);
 		let fromBalanceBefore = await getTokenAccountBalance(fromAccount);
 		let storeBalanceBefore = await getTokenAccountBalance(storeTokenAccount.publicKey);
+		let receiptBalanceBefore = await getTokenAccountBalance(receiptAccount);
 
 		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
 			[Buffer.from("user"), owner.publicKey.toBuffer(), priceAcc.publicKey.toBuffer(), program.programId.toBuffer()], 
@@ -64,10 +88,13 @@ describe('price-tracker', () => {
 			accounts: {
 				userPda: userPda,
 				priceAccount: priceAcc.publicKey,
+				vault: vault.publicKey,
 				storeMint: storeMint.publicKey,
 				userTokenAccount: fromAccount,
+				receiptAccount: receiptAccount,
                 store: storeTokenAccount.publicKey,
                 storeAuthorityPda: authorityPda,
+				lockedMint: lockedMint.publicKey,
 				signer: owner.publicKey,
 				tokenProgram: TOKEN_PROGRAM_ID,
 				systemProgram: SystemProgram.programId,
@@ -78,19 +105,24 @@ describe('price-tracker', () => {
 
 		let fromBalanceAfter = await getTokenAccountBalance(fromAccount);
 		let storeBalanceAfter = await getTokenAccountBalance(storeTokenAccount.publicKey);
+		let receiptBalanceAfter = await getTokenAccountBalance(receiptAccount);
 
 		assert.equal(fromBalanceBefore.sub(fromBalanceAfter).toString(), votes.toString());
 		assert.equal(storeBalanceAfter.sub(storeBalanceBefore).toString(), votes.toString());
+		assert.equal(receiptBalanceAfter.sub(receiptBalanceBefore).toString(), votes.toString());
 	}
 
-	const removeVotes = async (votes, toAccount, owner=payer, priceAcc=priceAccount) => {
+	// The owner burns their receipts to take back the voter's votes, which are their own by default
+	const removeVotes = async (votes, toAccount, owner=payer, priceAcc=priceAccount, voter=owner.publicKey) => {
 		votes = new BN((votes * 10**DECIMALS).toString());
 
+		let receiptAccount = await getReceiptAccount(owner.publicKey);
 		let toBalanceBefore = await getTokenAccountBalance(toAccount);
 		let storeBalanceBefore = await getTokenAccountBalance(storeTokenAccount.publicKey);
+		let receiptBalanceBefore = await getTokenAccountBalance(receiptAccount);
 
 		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
-			[Buffer.from("user"), owner.publicKey.toBuffer(), priceAcc.publicKey.toBuffer(), program.programId.toBuffer()], 
+			[Buffer.from("user"), voter.toBuffer(), priceAcc.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
 
@@ -98,10 +130,14 @@ describe('price-tracker', () => {
 			accounts: {
 				userPda: userPda,
 				priceAccount: priceAcc.publicKey,
+				vault: vault.publicKey,
 				storeMint: storeMint.publicKey,
 				userTokenAccount: toAccount,
+				receiptAccount: receiptAccount,
+				voter: voter,
                 store: storeTokenAccount.publicKey,
                 storeAuthorityPda: authorityPda,
+				lockedMint: lockedMint.publicKey,
 				signer: owner.publicKey,
 				tokenProgram: TOKEN_PROGRAM_ID,
 			},
@@ -110,9 +146,11 @@ describe('price-tracker', () => {
 
 		let toBalanceAfter = await getTokenAccountBalance(toAccount);
 		let storeBalanceAfter = await getTokenAccountBalance(storeTokenAccount.publicKey);
+		let receiptBalanceAfter = await getTokenAccountBalance(receiptAccount);
 
 		assert.equal(toBalanceAfter.sub(toBalanceBefore).toString(), votes.toString());
 		assert.equal(storeBalanceBefore.sub(storeBalanceAfter).toString(), votes.toString());
+		assert.equal(receiptBalanceBefore.sub(receiptBalanceAfter).toString(), votes.toString());
 	}
 
 	const setMinPrice = async (price, owner=provider.wallet.payer) => {
@@ -127,9 +165,22 @@ describe('price-tracker', () => {
 		});
 	}
 
+	const setVoteSettings = async (quorum, minBand, maxBand, owner=provider.wallet.payer, warmUpPeriod=0, cooldownPeriod=0) => {
+		await program.rpc.setVoteSettings(authorityPdaBump, new BN(quorum), new BN(minBand), new BN(maxBand), new BN(warmUpPeriod), new BN(cooldownPeriod), {
+			accounts: {
+				priceAccount: priceAccount.publicKey,
+                storeAuthorityPda: authorityPda,
+				storeMint: storeMint.publicKey,
+				authority: owner.publicKey
+			},
+			signers: [owner]
+		});
+	}
+
 	const checkTokenInfo = async (totalVotes, reserveTotal, minimumPrice) => {
+		// The reserve is the exact sum of votes * price, both of which are scaled
 		totalVotes = new BN((totalVotes * 10**DECIMALS).toString())
-		reserveTotal = new BN((reserveTotal * 10**DECIMALS).toString())
+		reserveTotal = new BN((reserveTotal * 10**DECIMALS).toString()).mul(new BN(10**DECIMALS))
 		minimumPrice = new BN((minimumPrice * 10**DECIMALS).toString())
 
 		let tokenInfo = await program.account.tokenInfo.fetch(authorityPda);
@@ -137,11 +188,26 @@ describe('price-tracker', () => {
 		assert.equal(tokenInfo.totalVotes.toString(), totalVotes.toString());
 		assert.equal(tokenInfo.minPrice.toString(), minimumPrice.toString());
 
+		// The totals always match the sum over every voter
+		let userVotes = new BN(0);
+		let userReserve = new BN(0);
+		for (let { account } of await program.account.userInfo.all()) {
+			userVotes = userVotes.add(account.votes);
+			// Delegated votes count at the price of their delegate
+			if (account.delegate === null) {
+				userReserve = userReserve.add(account.votes.add(account.delegatedVotes).mul(account.price));
+			}
+		}
+		assert.equal(userVotes.toString(), tokenInfo.totalVotes.toString());
+		assert.equal(userReserve.toString(), tokenInfo.reserveTotal.toString());
+
 		// These should always remain the same
 		assert.equal(tokenInfo.mint.toString(), storeMint.publicKey.toString());
 		assert.equal(tokenInfo.store.toString(), storeTokenAccount.publicKey.toString());
 		assert.equal(tokenInfo.lockedMint.toString(), lockedMint.publicKey.toString());
 		assert.equal(tokenInfo.authority.toString(), provider.wallet.publicKey.toString());
+		assert.equal(tokenInfo.vault.toString(), vault.publicKey.toString());
+		assert.equal(tokenInfo.finalized, false);
 	}
 
 	const checkUserInfo = async (votes, price, owner=payer) => {
@@ -166,28 +232,34 @@ describe('price-tracker', () => {
 
 	before(async () => {
 
-		// Initialize the tokens we need
-		lockedMint = await createMint(0); // NFT
-		redeemMint = await createMint(9); // Token used to buy-out NFT (likely WSOL or USDC)
-		storeMint = await createMint(9); // Fraction token
-
 		[authorityPda, authorityPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
 			[Buffer.from("price"), priceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
 
+		// Initialize the tokens we need
+		redeemMint = await createMint(9); // Token used to buy-out NFT (likely WSOL or USDC)
+		storeMint = await createMint(9); // Fraction token
+		lockedMint = await splToken.Token.createMint( // Receipts for staked fraction tokens
+			provider.connection, provider.wallet.payer, 
+			authorityPda, // mint authority
+			null, // freeze authority
+			9, splToken.TOKEN_PROGRAM_ID,
+		);
+
 	})
 
 	it('Initialize', async () => {
 
         userTokenAccount = await storeMint.createAccount(user);
 		await storeMint.mintTo(userTokenAccount, user, [], 100 * 10**DECIMALS);
-		await program.rpc.initialize(authorityPdaBump, minPrice, { 
+		await program.rpc.initialize(authorityPdaBump, minPrice, { quorum, minBand, maxBand, priceMode: { mean: {} }, warmUpPeriod: new BN(0), cooldownPeriod: new BN(0) }, { 
 			accounts: {
 				priceAccount: priceAccount.publicKey,
 				storeMint: storeMint.publicKey,
 				redeemMint: redeemMint.publicKey,
 				lockedMint: lockedMint.publicKey,
+				vault: vault.publicKey,
                 store: storeTokenAccount.publicKey,
                 storeAuthorityPda: authorityPda,
 				signer: user,
@@ -200,6 +272,41 @@ describe('price-tracker', () => {
 
     })
 
+	it('Check the vault cannot exist before its price account', async () => {
+		const otherPriceAccount = anchor.web3.Keypair.generate();
+		const otherStore = anchor.web3.Keypair.generate();
+		const [otherPda, otherPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("price"), otherPriceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		const otherLockedMint = await splToken.Token.createMint(
+			provider.connection, provider.wallet.payer, otherPda, null, 9, splToken.TOKEN_PROGRAM_ID,
+		);
+
+		// An account which already exists, and is not a vault created for this price account
+		try {
+			await program.rpc.initialize(otherPdaBump, minPrice, { quorum, minBand, maxBand, priceMode: { mean: {} }, warmUpPeriod: new BN(0), cooldownPeriod: new BN(0) }, { 
+				accounts: {
+					priceAccount: otherPriceAccount.publicKey,
+					storeMint: storeMint.publicKey,
+					redeemMint: redeemMint.publicKey,
+					lockedMint: otherLockedMint.publicKey,
+					vault: storeMint.publicKey,
+					store: otherStore.publicKey,
+					storeAuthorityPda: otherPda,
+					signer: user,
+					tokenProgram: TOKEN_PROGRAM_ID,
+					systemProgram: SystemProgram.programId,
+					rent: SYSVAR_RENT_PUBKEY,
+				},
+				signers: [otherStore, otherPriceAccount]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "307: The vault is not a token vault priced by this price account");
+		}
+	});
+
 	it('Check initial tokenInfo state', async () => {
 		let tokenInfo = await program.account.tokenInfo.fetch(authorityPda);
 		assert.equal(tokenInfo.reserveTotal.toString(), '0');
@@ -209,6 +316,18 @@ describe('price-tracker', () => {
 		assert.equal(tokenInfo.store.toString(), storeTokenAccount.publicKey.toString());
 		assert.equal(tokenInfo.lockedMint.toString(), lockedMint.publicKey.toString());
 		assert.equal(tokenInfo.authority.toString(), provider.wallet.publicKey.toString());
+		assert.equal(tokenInfo.vault.toString(), vault.publicKey.toString());
+		assert.equal(tokenInfo.finalized, false);
+		assert.equal(tokenInfo.quorum.toString(), quorum.toString());
+		assert.equal(tokenInfo.minBand.toString(), minBand.toString());
+		assert.equal(tokenInfo.maxBand.toString(), maxBand.toString());
+		assert.equal(tokenInfo.excludedAccounts.length, 0);
+		assert.equal(tokenInfo.priceScale.toString(), (10**9).toString());
+		assert.equal(tokenInfo.fractionScale.toString(), (10**9).toString());
+		assert.deepEqual(tokenInfo.priceMode, { mean: {} });
+		assert.equal(tokenInfo.priceBuckets.toString(), anchor.web3.PublicKey.default.toString());
+		assert.equal(tokenInfo.warmUpPeriod.toString(), '0');
+		assert.equal(tokenInfo.cooldownPeriod.toString(), '0');
 	});
 
 	it('Check initial externalPriceInfo state', async () => {
@@ -266,7 +385,7 @@ describe('price-tracker', () => {
 			await addVotes(1, 10, userTokenAccount);
 			assert.ok(false);
 		} catch (err) {
-			assert.equal(err.message, "305: The price must be greater than 1/5th the current weighted average");
+			assert.equal(err.message, "305: The price must be within the allowed band below the current weighted average");
 		}
 	});
 
@@ -275,7 +394,7 @@ describe('price-tracker', () => {
 			await addVotes(1, 500, userTokenAccount);
 			assert.ok(false);
 		} catch (err) {
-			assert.equal(err.message, "303: The price must be less than 5x the current weighted average");
+			assert.equal(err.message, "303: The price must be within the allowed band above the current weighted average");
 		}
 	});
 
@@ -319,6 +438,205 @@ describe('price-tracker', () => {
 		}
 	});
 
+	it('Check only the auction program can settle an offer', async () => {
+		let badActor = anchor.web3.Keypair.generate();
+		try {
+			await program.rpc.settleOffer(new BN(1), {
+				accounts: {
+					priceAccount: priceAccount.publicKey,
+					auctionAuthority: badActor.publicKey,
+				},
+				signers: [badActor]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "An address constraint was violated");
+		}
+	});
+
+	it('Receipts are minted for every staked vote', async () => {
+		let mintInfo = await lockedMint.getMintInfo();
+		let tokenInfo = await program.account.tokenInfo.fetch(authorityPda);
+		assert.equal(mintInfo.supply.toString(), tokenInfo.totalVotes.toString());
+	});
+
+	it('Check a receipt holder can take back the votes behind their receipts', async () => {
+		let holder = anchor.web3.Keypair.generate();
+		await connection.confirmTransaction(await connection.requestAirdrop(holder.publicKey, 1e9));
+		let holderTokenAccount = await storeMint.createAccount(holder.publicKey);
+		let holderReceiptAccount = await getReceiptAccount(holder.publicKey);
+		let payerReceiptAccount = await getReceiptAccount(payer.publicKey);
+
+		// Without receipts the holder cannot touch anybody's votes
+		try {
+			await removeVotes(1, holderTokenAccount, holder, priceAccount, payer.publicKey);
+			assert.ok(false);
+		} catch (err) {}
+
+		// Nor can the voter take back votes whose receipts they gave away
+		await lockedMint.transfer(payerReceiptAccount, holderReceiptAccount, payer, [], 1 * 10**DECIMALS);
+		let receipts = await getTokenAccountBalance(payerReceiptAccount);
+		try {
+			await removeVotes(receipts.toNumber() / 10**DECIMALS + 1, userTokenAccount);
+			assert.ok(false);
+		} catch (err) {}
+
+		// The holder burns the receipts and the fractions behind them come out of the voter's votes
+		await removeVotes(1, holderTokenAccount, holder, priceAccount, payer.publicKey);
+		assert.equal((await getTokenAccountBalance(holderTokenAccount)).toString(), (1 * 10**DECIMALS).toString());
+		await checkTokenInfo(51, 51 * 20, 5);
+		await checkUserInfo(51, 20);
+		await checkExternalPriceInfo(20, true);
+	});
+
+	it('Check only authority can change vote settings', async () => {
+		let badActor = anchor.web3.Keypair.generate();
+		try {
+			await setVoteSettings(1_000, 0, 100_000, badActor);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has_one constraint was violated");
+		}
+	});
+
+	it('Check vote settings must be valid', async () => {
+		try {
+			await setVoteSettings(0, 2_000, 50_000);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "311: The quorum must be more than zero and at most the whole supply");
+		}
+		try {
+			await setVoteSettings(5_000, 12_000, 50_000);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "312: The price band must include the current weighted average");
+		}
+	});
+
+	// 51/100 votes fall short of a 60% supermajority, which applies without waiting for a vote
+	it('Raise the quorum', async () => {
+		await setVoteSettings(6_000, 2_000, 50_000);
+		await checkExternalPriceInfo(20, false);
+
+		await setVoteSettings(5_000, 2_000, 50_000);
+		await checkExternalPriceInfo(20, true);
+	});
+
+	it('Narrow the price band', async () => {
+		await setVoteSettings(5_000, 9_000, 11_000);
+		try {
+			await addVotes(0, 25, userTokenAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "303: The price must be within the allowed band above the current weighted average");
+		}
+		await setVoteSettings(5_000, 2_000, 50_000);
+	});
+
+	// Borrowed fractions can't be voted and taken straight back to flip allowedToCombine
+	it('Votes only count after the warm-up period and are locked for the cooldown', async () => {
+		await setVoteSettings(5_000, 2_000, 50_000, payer, 1, 2);
+
+		// Staked, but not yet counted
+		await addVotes(1, 20, userTokenAccount);
+		await checkTokenInfo(51, 51 * 20, 5);
+		await checkUserInfo(51, 20);
+		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("user"), payer.publicKey.toBuffer(), priceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		let userInfo = await program.account.userInfo.fetch(userPda);
+		assert.equal(userInfo.pendingVotes.toString(), (1 * 10**DECIMALS).toString());
+
+		try {
+			await removeVotes(1, userTokenAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "315: Votes cannot be removed until the cooldown after the last vote has passed");
+		}
+
+		// Anyone can count the votes once they have warmed up
+		await new Promise(r => setTimeout(r, 3000));
+		await program.rpc.activateVotes(authorityPdaBump, userPdaBump, {
+			accounts: {
+				priceAccount: priceAccount.publicKey,
+				vault: vault.publicKey,
+				voter: payer.publicKey,
+				userPda: userPda,
+				storeAuthorityPda: authorityPda,
+				storeMint: storeMint.publicKey,
+			},
+		});
+		await checkTokenInfo(52, 52 * 20, 5);
+		await checkUserInfo(52, 20);
+
+		await removeVotes(1, userTokenAccount);
+		await checkTokenInfo(51, 51 * 20, 5);
+		await setVoteSettings(5_000, 2_000, 50_000);
+	});
+
+	it('Delegated votes follow the price of their delegate', async () => {
+		let delegate = anchor.web3.Keypair.generate();
+		await connection.confirmTransaction(await connection.requestAirdrop(delegate.publicKey, 1e9));
+		let delegateTokenAccount = await storeMint.createAccount(delegate.publicKey);
+		await storeMint.transfer(userTokenAccount, delegateTokenAccount, payer, [], 10 * 10**DECIMALS);
+
+		const findUserPda = async (voter) => {
+			return await anchor.web3.PublicKey.findProgramAddress(
+				[Buffer.from("user"), voter.toBuffer(), priceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+				program.programId
+			);
+		}
+		let [userPda, userPdaBump] = await findUserPda(payer.publicKey);
+		let [delegatePda, delegatePdaBump] = await findUserPda(delegate.publicKey);
+		let [delegation, delegationBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("delegation"), payer.publicKey.toBuffer(), priceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		let accounts = {
+			priceAccount: priceAccount.publicKey,
+			vault: vault.publicKey,
+			userPda: userPda,
+			delegate: delegate.publicKey,
+			delegatePda: delegatePda,
+			delegation: delegation,
+			storeAuthorityPda: authorityPda,
+			signer: payer.publicKey,
+		};
+
+		await addVotes(10, 25, delegateTokenAccount, delegate);
+		await checkTokenInfo(61, 51 * 20 + 10 * 25, 5);
+
+		await program.rpc.delegateVotes(authorityPdaBump, userPdaBump, delegatePdaBump, delegationBump, {
+			accounts: { ...accounts, rent: SYSVAR_RENT_PUBKEY, systemProgram: SystemProgram.programId },
+		});
+		await checkTokenInfo(61, 61 * 25, 5);
+		await checkExternalPriceInfo(25, true);
+
+		// The delegate moves the delegated votes along with their own
+		await addVotes(0, 30, delegateTokenAccount, delegate);
+		await checkTokenInfo(61, 61 * 30, 5);
+		await checkExternalPriceInfo(30, true);
+		let delegateInfo = await program.account.userInfo.fetch(delegatePda);
+		assert.equal(delegateInfo.delegatedVotes.toString(), (51 * 10**DECIMALS).toString());
+
+		try {
+			await removeVotes(1, userTokenAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "316: The votes are delegated, the delegation has to be revoked first");
+		}
+
+		// Revoking puts the votes back at the delegator's own price
+		await program.rpc.revokeDelegation(authorityPdaBump, userPdaBump, delegatePdaBump, { accounts });
+		await checkTokenInfo(61, 51 * 20 + 10 * 30, 5);
+		await checkUserInfo(51, 20);
+
+		await removeVotes(10, delegateTokenAccount, delegate);
+		await checkTokenInfo(51, 51 * 20, 5);
+	});
+
 	it("Check storeMint constraint", async () => {
 
 		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
@@ -330,10 +648,13 @@ describe('price-tracker', () => {
 				accounts: {
 					userPda: userPda,
 					priceAccount: priceAccount.publicKey,
+					vault: vault.publicKey,
 					storeMint: redeemMint.publicKey,
 					userTokenAccount: userTokenAccount,
+					receiptAccount: await getReceiptAccount(payer.publicKey),
 					store: storeTokenAccount.publicKey,
 					storeAuthorityPda: authorityPda,
+					lockedMint: lockedMint.publicKey,
 					signer: payer.publicKey,
 					tokenProgram: TOKEN_PROGRAM_ID,
 					systemProgram: SystemProgram.programId,
@@ -348,6 +669,71 @@ describe('price-tracker', () => {
 		
 	})
 
+	it("Check votes must be for the vault being priced", async () => {
+
+		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("user"), payer.publicKey.toBuffer(), priceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		try {
+			await program.rpc.addVote(authorityPdaBump, userPdaBump, new BN(0), minPrice, {
+				accounts: {
+					userPda: userPda,
+					priceAccount: priceAccount.publicKey,
+					vault: anchor.web3.Keypair.generate().publicKey,
+					storeMint: storeMint.publicKey,
+					userTokenAccount: userTokenAccount,
+					receiptAccount: await getReceiptAccount(payer.publicKey),
+					store: storeTokenAccount.publicKey,
+					storeAuthorityPda: authorityPda,
+					lockedMint: lockedMint.publicKey,
+					signer: payer.publicKey,
+					tokenProgram: TOKEN_PROGRAM_ID,
+					systemProgram: SystemProgram.programId,
+					rent: SYSVAR_RENT_PUBKEY,
+				},
+				signers: [payer]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A raw constraint was violated");
+		}
+		
+	})
+
+	it("Check the accounts excluded from the quorum must match those recorded", async () => {
+
+		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("user"), payer.publicKey.toBuffer(), priceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		try {
+			await program.rpc.addVote(authorityPdaBump, userPdaBump, new BN(0), minPrice, {
+				accounts: {
+					userPda: userPda,
+					priceAccount: priceAccount.publicKey,
+					vault: vault.publicKey,
+					storeMint: storeMint.publicKey,
+					userTokenAccount: userTokenAccount,
+					receiptAccount: await getReceiptAccount(payer.publicKey),
+					store: storeTokenAccount.publicKey,
+					storeAuthorityPda: authorityPda,
+					lockedMint: lockedMint.publicKey,
+					signer: payer.publicKey,
+					tokenProgram: TOKEN_PROGRAM_ID,
+					systemProgram: SystemProgram.programId,
+					rent: SYSVAR_RENT_PUBKEY,
+				},
+				remainingAccounts: [{ pubkey: userTokenAccount, isWritable: false, isSigner: false }],
+				signers: [payer]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "313: The accounts excluded from the supply do not match those of the price account");
+		}
+		
+	})
+
 	it("Check fromAccount's mint must be the storeMint", async () => {
 		let wrongTokenAccount = await redeemMint.createAccount(user);
 
@@ -361,10 +747,13 @@ describe('price-tracker', () => {
 				accounts: {
 					userPda: userPda,
 					priceAccount: priceAccount.publicKey,
+					vault: vault.publicKey,
 					storeMint: storeMint.publicKey,
 					userTokenAccount: wrongTokenAccount,
+					receiptAccount: await getReceiptAccount(payer.publicKey),
 					store: storeTokenAccount.publicKey,
 					storeAuthorityPda: authorityPda,
+					lockedMint: lockedMint.publicKey,
 					signer: payer.publicKey,
 					tokenProgram: TOKEN_PROGRAM_ID,
 					systemProgram: SystemProgram.programId,
@@ -430,4 +819,234 @@ describe('price-tracker', () => {
 
 	})
 
+	it("Check staked votes can only be redeemed for the vault being priced", async () => {
+		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("user"), payer.publicKey.toBuffer(), priceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		let [redeemAccount, redeemAccountBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("redeem"), priceAccount.publicKey.toBuffer()], 
+			program.programId
+		);
+		const redeem = async (destinationTokenAccount) => {
+			await program.rpc.redeemVotes(authorityPdaBump, userPdaBump, redeemAccountBump, {
+				accounts: {
+					priceAccount: priceAccount.publicKey,
+					store: storeTokenAccount.publicKey,
+					receiptAccount: await getReceiptAccount(payer.publicKey),
+					voter: payer.publicKey,
+					userPda: userPda,
+					storeAuthorityPda: authorityPda,
+					redeemAccount: redeemAccount,
+					storeMint: storeMint.publicKey,
+					lockedMint: lockedMint.publicKey,
+					destinationTokenAccount: destinationTokenAccount,
+					signer: payer.publicKey,
+					auction: priceAccount.publicKey,
+					settings: priceAccount.publicKey,
+					vault: priceAccount.publicKey,
+					paymentTreasury: destinationTokenAccount,
+					redeemTreasury: destinationTokenAccount,
+					paymentMint: redeemMint.publicKey,
+					temporaryTokenAccount: destinationTokenAccount,
+					vaultPda: priceAccount.publicKey,
+					vaultProgram: priceAccount.publicKey,
+					auctionProgram: priceAccount.publicKey,
+					tokenProgram: TOKEN_PROGRAM_ID,
+					rent: SYSVAR_RENT_PUBKEY,
+					systemProgram: SystemProgram.programId,
+				},
+				signers: [payer]
+			});
+		}
+
+		// The proceeds can only be paid into an account of the payment mint
+		try {
+			await redeem(await storeMint.createAccount(user));
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A raw constraint was violated");
+		}
+
+		// The price account is not a token vault, let alone a combined one
+		try {
+			await redeem(await redeemMint.createAccount(user));
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "307: The vault is not a token vault priced by this price account");
+		}
+	})
+
+	// A vault priced in a 6 decimal token, such as USDC, takes votes in the same terms
+	// but sets its price per share in base units of the 6 decimal mint
+	it("Scale the price per share by the decimals of the mints", async () => {
+		const usdcPriceAccount = anchor.web3.Keypair.generate();
+		const usdcStore = anchor.web3.Keypair.generate();
+		const usdcMint = await createMint(6);
+		const [usdcPda, usdcPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("price"), usdcPriceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		const usdcLockedMint = await splToken.Token.createMint(
+			provider.connection, provider.wallet.payer, usdcPda, null, 9, splToken.TOKEN_PROGRAM_ID,
+		);
+
+		await program.rpc.initialize(usdcPdaBump, minPrice, { quorum, minBand, maxBand, priceMode: { mean: {} }, warmUpPeriod: new BN(0), cooldownPeriod: new BN(0) }, { 
+			accounts: {
+				priceAccount: usdcPriceAccount.publicKey,
+				storeMint: storeMint.publicKey,
+				redeemMint: usdcMint.publicKey,
+				lockedMint: usdcLockedMint.publicKey,
+				vault: vault.publicKey,
+                store: usdcStore.publicKey,
+                storeAuthorityPda: usdcPda,
+				signer: user,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+                rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [usdcStore, usdcPriceAccount]
+		});
+
+		let tokenInfo = await program.account.tokenInfo.fetch(usdcPda);
+		assert.equal(tokenInfo.priceScale.toString(), (10**6).toString());
+		assert.equal(tokenInfo.fractionScale.toString(), (10**9).toString());
+
+		let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("user"), user.toBuffer(), usdcPriceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		let votes = new BN(1 * 10**DECIMALS);
+		let price = new BN(20 * 10**DECIMALS);
+		await program.rpc.addVote(usdcPdaBump, userPdaBump, votes, price, {
+			accounts: {
+				userPda: userPda,
+				priceAccount: usdcPriceAccount.publicKey,
+				vault: vault.publicKey,
+				storeMint: storeMint.publicKey,
+				userTokenAccount: userTokenAccount,
+				receiptAccount: (await usdcLockedMint.getOrCreateAssociatedAccountInfo(user)).address,
+                store: usdcStore.publicKey,
+                storeAuthorityPda: usdcPda,
+				lockedMint: usdcLockedMint.publicKey,
+				signer: user,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+                rent: SYSVAR_RENT_PUBKEY,
+			},
+		});
+
+		// 1000x less than it would be for a 9 decimal price mint
+		let externalPriceInfo = await program.account.externalPriceAccount.fetch(usdcPriceAccount.publicKey);
+		assert.equal(externalPriceInfo.pricePerShare.toString(), (20 * 10**6).toString());
+		assert.equal(externalPriceInfo.priceMint.toString(), usdcMint.publicKey.toString());
+	})
+
+	// A whale voting at the top of the band drags the mean up, but not the median
+	it("Price a vault at the median vote", async () => {
+		const medianPriceAccount = anchor.web3.Keypair.generate();
+		const medianStore = anchor.web3.Keypair.generate();
+		const [medianPda, medianPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("price"), medianPriceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+			program.programId
+		);
+		const priceBuckets = anchor.web3.Keypair.generate();
+		const medianLockedMint = await splToken.Token.createMint(
+			provider.connection, provider.wallet.payer, medianPda, null, 9, splToken.TOKEN_PROGRAM_ID,
+		);
+
+		await program.rpc.initialize(medianPdaBump, minPrice, { quorum, minBand, maxBand, priceMode: { median: {} }, warmUpPeriod: new BN(0), cooldownPeriod: new BN(0) }, { 
+			accounts: {
+				priceAccount: medianPriceAccount.publicKey,
+				storeMint: storeMint.publicKey,
+				redeemMint: redeemMint.publicKey,
+				lockedMint: medianLockedMint.publicKey,
+				vault: vault.publicKey,
+                store: medianStore.publicKey,
+                storeAuthorityPda: medianPda,
+				signer: user,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+                rent: SYSVAR_RENT_PUBKEY,
+			},
+			signers: [medianStore, medianPriceAccount]
+		});
+
+		const vote = async (voter, votes, price) => {
+			let voterTokenAccount = await storeMint.createAccount(voter.publicKey);
+			await storeMint.mintTo(voterTokenAccount, user, [], votes * 10**DECIMALS);
+			let [userPda, userPdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+				[Buffer.from("user"), voter.publicKey.toBuffer(), medianPriceAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
+				program.programId
+			);
+			await program.rpc.addVote(medianPdaBump, userPdaBump, new BN(votes * 10**DECIMALS), new BN(price * 10**DECIMALS), {
+				accounts: {
+					userPda: userPda,
+					priceAccount: medianPriceAccount.publicKey,
+					vault: vault.publicKey,
+					storeMint: storeMint.publicKey,
+					userTokenAccount: voterTokenAccount,
+					receiptAccount: (await medianLockedMint.getOrCreateAssociatedAccountInfo(voter.publicKey)).address,
+					store: medianStore.publicKey,
+					storeAuthorityPda: medianPda,
+					lockedMint: medianLockedMint.publicKey,
+					signer: voter.publicKey,
+					tokenProgram: TOKEN_PROGRAM_ID,
+					systemProgram: SystemProgram.programId,
+					rent: SYSVAR_RENT_PUBKEY,
+				},
+				remainingAccounts: [{ pubkey: priceBuckets.publicKey, isWritable: true, isSigner: false }],
+				signers: [voter]
+			});
+		}
+
+		// Votes are only taken once the buckets exist
+		try {
+			await vote(payer, 2, 10);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.message, "314: The price buckets are not those of the price account");
+		}
+
+		await initPriceBuckets(medianPriceAccount, medianPda, medianPdaBump, priceBuckets);
+
+		let tokenInfo = await program.account.tokenInfo.fetch(medianPda);
+		assert.deepEqual(tokenInfo.priceMode, { median: {} });
+		assert.equal(tokenInfo.priceBuckets.toString(), priceBuckets.publicKey.toString());
+
+		// Nor can the buckets be swapped for empty ones once they are set
+		try {
+			await initPriceBuckets(medianPriceAccount, medianPda, medianPdaBump, anchor.web3.Keypair.generate());
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A raw constraint was violated");
+		}
+
+		let holder = anchor.web3.Keypair.generate();
+		let whale = anchor.web3.Keypair.generate();
+		for (let voter of [holder, whale]) {
+			await connection.confirmTransaction(await connection.requestAirdrop(voter.publicKey, 1e9));
+		}
+
+		await vote(payer, 2, 10);
+		await vote(holder, 1, 11.25);
+		await vote(whale, 2, 40);
+
+		// The mean of (2 * 10 + 11.25 + 2 * 40) / 5 would be 22.25
+		let externalPriceInfo = await program.account.externalPriceAccount.fetch(medianPriceAccount.publicKey);
+		assert.equal(externalPriceInfo.pricePerShare.toString(), new BN(11.25 * 10**DECIMALS).toString());
+		tokenInfo = await program.account.tokenInfo.fetch(medianPda);
+		assert.equal(tokenInfo.medianPrice.toString(), new BN(11.25 * 10**DECIMALS).toString());
+
+		// 11.125 and 11.25 share a bucket, so the median is its average rather than the exact 11.125,
+		// but never more than 1/2^PRICE_BUCKET_BITS away from it
+		let minnow = anchor.web3.Keypair.generate();
+		await connection.confirmTransaction(await connection.requestAirdrop(minnow.publicKey, 1e9));
+		await vote(minnow, 1, 11.125);
+		tokenInfo = await program.account.tokenInfo.fetch(medianPda);
+		assert.equal(tokenInfo.medianPrice.toString(), new BN(11.1875 * 10**DECIMALS).toString());
+		let exactMedian = new BN(11.125 * 10**DECIMALS);
+		assert.ok(tokenInfo.medianPrice.sub(exactMedian).abs().muln(32).lt(exactMedian));
+	})
+
 })
\ No newline at end of file